@@ -0,0 +1,165 @@
+use std::collections::BTreeMap as Map;
+
+use serde_json::Value;
+
+use crate::edition::Edition;
+
+// Hosts used to build archive.org URLs. Overridable so tests can point at a local stand-in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Archive {
+    host: String,
+    iiif_host: String,
+}
+
+impl Default for Archive {
+    fn default() -> Self {
+        Self::new("https://archive.org", "https://iiif.archive.org")
+    }
+}
+
+impl Archive {
+    pub fn new(host: &str, iiif_host: &str) -> Self {
+        Self {
+            host: String::from(host.trim_end_matches('/')),
+            iiif_host: String::from(iiif_host.trim_end_matches('/')),
+        }
+    }
+
+    pub fn details_url(&self, ocaid: &str) -> String {
+        format!("{}/details/{}", self.host, ocaid)
+    }
+
+    // Without a file name this is the item's directory listing
+    pub fn download_url(&self, ocaid: &str, file: Option<&str>) -> String {
+        match file {
+            Some(file) => format!("{}/download/{}/{}", self.host, ocaid, file),
+            None => format!("{}/download/{}", self.host, ocaid),
+        }
+    }
+
+    pub fn bookreader_url(&self, ocaid: &str) -> String {
+        format!("{}/details/{}/mode/2up", self.host, ocaid)
+    }
+
+    pub fn iiif_manifest_url(&self, ocaid: &str) -> String {
+        format!("{}/iiif/3/{}/manifest.json", self.iiif_host, ocaid)
+    }
+
+    pub fn metadata_url(&self, ocaid: &str) -> String {
+        format!("{}/metadata/{}", self.host, ocaid)
+    }
+}
+
+// The archive is passed in so a Client::set_archive stand-in is honoured, see Client::archive
+impl Edition {
+    pub fn archive_details_url(&self, archive: &Archive) -> Option<String> {
+        self.ocaid.as_ref().map(|ocaid| archive.details_url(ocaid))
+    }
+
+    // e.g. "pdf" or "epub" for the derived {ocaid}.pdf and {ocaid}.epub files
+    pub fn archive_download_url(&self, archive: &Archive, extension: &str) -> Option<String> {
+        self.ocaid
+            .as_ref()
+            .map(|ocaid| archive.download_url(ocaid, Some(&format!("{}.{}", ocaid, extension))))
+    }
+
+    pub fn archive_bookreader_url(&self, archive: &Archive) -> Option<String> {
+        self.ocaid.as_ref().map(|ocaid| archive.bookreader_url(ocaid))
+    }
+
+    pub fn archive_iiif_manifest_url(&self, archive: &Archive) -> Option<String> {
+        self.ocaid.as_ref().map(|ocaid| archive.iiif_manifest_url(ocaid))
+    }
+}
+
+// https://archive.org/metadata/ithacapurgatorio00dant, unknown identifiers come back as {}
+#[derive(Default, Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct ArchiveItem {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub server: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dir: Option<String>,
+    #[serde(default)]
+    pub files: Vec<ArchiveFile>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub item_size: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_dark: Option<bool>,
+    // Item level metadata, most values are strings or lists of strings
+    #[serde(default)]
+    pub metadata: Map<String, Value>,
+}
+
+impl ArchiveItem {
+    pub fn exists(&self) -> bool {
+        !self.metadata.is_empty()
+    }
+
+    pub fn title(&self) -> Option<String> {
+        self.metadata.get("title").and_then(first_string)
+    }
+
+    // Lending library items are flagged with access-restricted-item
+    pub fn access_restricted(&self) -> bool {
+        self.is_dark.unwrap_or(false)
+            || self.metadata.get("access-restricted-item").and_then(first_string).as_deref() == Some("true")
+    }
+
+    pub fn collections(&self) -> Vec<String> {
+        match self.metadata.get("collection") {
+            Some(Value::Array(collections)) => collections.iter().filter_map(first_string).collect(),
+            Some(collection) => first_string(collection).into_iter().collect(),
+            None => Vec::new(),
+        }
+    }
+
+    pub fn formats(&self) -> Vec<String> {
+        let mut formats: Vec<String> = self.files.iter().filter_map(|file| file.format.clone()).collect();
+        formats.sort();
+        formats.dedup();
+
+        formats
+    }
+
+    pub fn files_with_format(&self, format: &str) -> Vec<&ArchiveFile> {
+        self.files.iter().filter(|file| file.format.as_deref() == Some(format)).collect()
+    }
+}
+
+fn first_string(value: &Value) -> Option<String> {
+    match value {
+        Value::String(value) => Some(value.clone()),
+        Value::Array(values) => values.first().and_then(first_string),
+        _ => None,
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct ArchiveFile {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    // archive.org sends sizes and timestamps as strings
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mtime: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub md5: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub private: Option<String>,
+}
+
+impl ArchiveFile {
+    pub fn size_bytes(&self) -> Option<u64> {
+        self.size.as_ref().and_then(|size| size.parse().ok())
+    }
+
+    pub fn is_private(&self) -> bool {
+        self.private.as_deref() == Some("true")
+    }
+}
@@ -1,14 +1,11 @@
-use std::fmt;
-use std::collections::BTreeMap as Map;
-use std::marker::PhantomData;
-use std::str::FromStr;
+use serde_json::{Map, Value};
 
-use serde::{Deserialize, Deserializer};
-use serde::de::{self, Visitor, MapAccess};
-use void::Void;
+use crate::canonical::{is_default, read_record, write_record, Elided};
+use crate::date::{Created, LastModified};
 
 #[derive(Default, Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[serde(remote = "Self")]
 pub struct Author {
     pub key: String,
     pub name: String,
@@ -26,29 +23,30 @@ pub struct Author {
     #[serde(rename = "death_date")]
     pub death_date: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub location: Option<Location>, //No Idea What this looks like in real
+    pub location: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub date: Option<Date>, //No Idea What this looks like in real
+    pub date: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "entity_type")]
-    pub entity_type: Option<EntityType>, //No Idea What this looks like in real
+    pub entity_type: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "fuller_name")]
-    pub fuller_name: Option<FullerName>, //No Idea What this looks like in real
+    pub fuller_name: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "personal_name")]
     pub personal_name: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub title: Option<String>, //According to https://openlibrary.org/authors/OL29497A.json looks like a String
+    // -1 marks a deleted photo, like in the covers of editions and works
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub photos: Option<Vec<usize>>,
+    pub photos: Option<Vec<isize>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub links: Option<Vec<Link>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "remote_ids")]
     pub remote_ids: Option<RemoteIds>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub wikipedia: Option<Wikipedia>, //No Idea What this looks like in real
+    pub wikipedia: Option<String>,
     pub revision: usize,
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "latest_revision")]
@@ -57,6 +55,27 @@ pub struct Author {
     pub created: Option<Created>,
     #[serde(rename = "last_modified")]
     pub last_modified: LastModified,
+    // Fields this crate does not model, kept so that saving a record does not drop them
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+    // Explicit nulls and empty values, kept so that saving a record does not drop them either
+    #[serde(skip)]
+    pub elided: Elided,
+}
+
+impl<'de> serde::Deserialize<'de> for Author {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let (mut record, elided) = read_record(deserializer, Author::deserialize)?;
+        record.elided = elided;
+
+        Ok(record)
+    }
+}
+
+impl serde::Serialize for Author {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        write_record(serializer, &self.elided, |value| Author::serialize(self, value))
+    }
 }
 
 #[derive(Default, Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
@@ -80,44 +99,6 @@ pub enum BioEnum {
     BioType(Bio)
 }
 
-#[derive(Default, Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct Location {
-    #[serde(rename = "type")]
-    pub type_field: String,
-    pub examples: Vec<String>,
-}
-
-#[derive(Default, Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct Date {
-    #[serde(rename = "type")]
-    pub type_field: String,
-}
-
-#[derive(Default, Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct EntityType {
-    #[serde(rename = "type")]
-    pub type_field: String,
-    #[serde(rename = "enum")]
-    pub enum_field: Vec<String>,
-}
-
-#[derive(Default, Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct FullerName {
-    #[serde(rename = "type")]
-    pub type_field: String,
-}
-
-#[derive(Default, Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct Title {
-    #[serde(rename = "type")]
-    pub type_field: String,
-}
-
 #[derive(Default, Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Link {
@@ -130,31 +111,17 @@ pub struct Link {
 #[derive(Default, Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RemoteIds {
+    #[serde(default)]
+    #[serde(skip_serializing_if = "is_default")]
     pub viaf: String,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "is_default")]
     pub wikidata: String,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "is_default")]
     pub isni: String,
+    // goodreads, librarything, amazon, storygraph and the other ids authors are linked to
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
-#[derive(Default, Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct Wikipedia {
-    #[serde(rename = "type")]
-    pub type_field: String,
-    pub description: String,
-}
-
-#[derive(Default, Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct Created {
-    #[serde(rename = "type")]
-    pub type_field: String,
-    pub value: String,
-}
-
-#[derive(Default, Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct LastModified {
-    #[serde(rename = "type")]
-    pub type_field: String,
-    pub value: String,
-}
@@ -0,0 +1,214 @@
+use std::collections::HashMap;
+use std::io::{self, Write};
+
+use crate::author::Author;
+use crate::client::process_olid_key;
+use crate::edition::Edition;
+use crate::entity::Entity;
+use crate::name::ascii_fold;
+use crate::work::Work;
+
+const MONTHS: [&str; 12] = ["jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec"];
+
+// Leading articles skipped when picking the title word of a citation key
+const ARTICLES: [&str; 12] = ["a", "an", "the", "le", "la", "les", "l", "der", "die", "das", "el", "il"];
+
+struct Entry {
+    key: String,
+    fields: Vec<(&'static str, String)>,
+}
+
+impl Entry {
+    fn new(entity: &Entity) -> Self {
+        let (edition, work, authors) = (&entity.edition, &entity.work, &entity.authors);
+        let mut fields = Vec::new();
+
+        if !authors.is_empty() {
+            let names: Vec<String> = authors.iter().map(bibtex_name).collect();
+            fields.push(("author", names.join(" and ")));
+        }
+
+        fields.push(("title", escape(&entity.full_title())));
+
+        if let Some(edition_name) = edition.edition_statement() {
+            fields.push(("edition", escape(edition_name)));
+        }
+        if let Some(series) = edition.series.as_ref().filter(|series| !series.is_empty()) {
+            fields.push(("series", escape(&series.join("; "))));
+        }
+        if let Some(publishers) = edition.publishers.as_ref().filter(|publishers| !publishers.is_empty()) {
+            fields.push(("publisher", escape(&publishers.join("; "))));
+        }
+        if let Some(places) = edition.publish_places.as_ref().filter(|places| !places.is_empty()) {
+            fields.push(("address", escape(&places.join("; "))));
+        }
+
+        let date = edition.parsed_publish_date();
+        let year = date.map(|date| date.year).or_else(|| work.first_publish_year());
+        if let Some(year) = year {
+            fields.push(("year", year.to_string()));
+        }
+        // Month macros are written unbraced so styles can localize them
+        if let Some(month) = date.and_then(|date| date.month) {
+            fields.push(("month", MONTHS[month as usize - 1].to_string()));
+        }
+
+        let isbn = edition
+            .isbn13
+            .iter()
+            .chain(edition.isbn10.iter())
+            .flatten()
+            .next();
+        if let Some(isbn) = isbn {
+            fields.push(("isbn", escape(isbn)));
+        }
+        if !edition.key.is_empty() {
+            fields.push(("url", format!("https://openlibrary.org{}", edition.key)));
+        }
+
+        Self {
+            key: citation_key(edition, authors, &title_word(entity.title_parts().0), year),
+            fields,
+        }
+    }
+
+    fn render(&self, key: &str) -> String {
+        let mut entry = format!("@book{{{},\n", key);
+        for (name, value) in self.fields.iter() {
+            if *name == "month" {
+                entry.push_str(&format!("  {} = {},\n", name, value));
+            } else {
+                entry.push_str(&format!("  {} = {{{}}},\n", name, value));
+            }
+        }
+        entry.push_str("}\n");
+
+        entry
+    }
+}
+
+impl Entity {
+    pub fn to_bibtex(&self) -> String {
+        let entry = Entry::new(self);
+
+        entry.render(&entry.key)
+    }
+}
+
+impl Edition {
+    // Without the work and author records the entry has no author field
+    pub fn to_bibtex(&self) -> String {
+        let entity = Entity::with_authors(process_olid_key(&self.key), self.clone(), Work::default(), Vec::new());
+        let entry = Entry::new(&entity);
+
+        entry.render(&entry.key)
+    }
+}
+
+// Writes one @book entry per entity. Citation keys that would collide get the suffixes a, b, c...
+pub fn write_bibliography<W: Write>(writer: &mut W, entities: &[Entity]) -> io::Result<()> {
+    let entries: Vec<Entry> = entities
+        .iter()
+        .map(Entry::new)
+        .collect();
+
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for entry in entries.iter() {
+        *counts.entry(entry.key.as_str()).or_insert(0) += 1;
+    }
+
+    let mut seen: HashMap<&str, usize> = HashMap::new();
+    for (index, entry) in entries.iter().enumerate() {
+        let key = if counts[entry.key.as_str()] > 1 {
+            let n = seen.entry(entry.key.as_str()).or_insert(0);
+            *n += 1;
+            format!("{}{}", entry.key, suffix(*n))
+        } else {
+            entry.key.clone()
+        };
+
+        if index > 0 {
+            writeln!(writer)?;
+        }
+        writer.write_all(entry.render(&key).as_bytes())?;
+    }
+
+    Ok(())
+}
+
+// "austen1813pride": the first author's family name, the year and the first title word
+fn citation_key(edition: &Edition, authors: &[Author], title_word: &str, year: Option<i32>) -> String {
+    let name = match authors.first() {
+        Some(author) if author.is_organization() => ascii_fold(&author.name.to_lowercase()),
+        Some(author) => ascii_fold(&author.personal_name().family_core().to_lowercase()),
+        None => String::new(),
+    };
+
+    let mut key = name;
+    if let Some(year) = year {
+        key.push_str(&year.to_string());
+    }
+    key.push_str(title_word);
+
+    if key.is_empty() {
+        key = process_olid_key(&edition.key);
+    }
+
+    key
+}
+
+fn title_word(title: &str) -> String {
+    title
+        .split(|c: char| c.is_whitespace() || c == '\'' || c == '’')
+        .map(|word| ascii_fold(&word.to_lowercase()))
+        .find(|word| !word.is_empty() && !ARTICLES.contains(&word.as_str()))
+        .unwrap_or_default()
+}
+
+// 1 -> "a", 26 -> "z", 27 -> "aa"
+fn suffix(mut n: usize) -> String {
+    let mut letters = Vec::new();
+    while n > 0 {
+        n -= 1;
+        letters.push((b'a' + (n % 26) as u8) as char);
+        n /= 26;
+    }
+
+    letters.iter().rev().collect()
+}
+
+// Organizations are braced so BibTeX does not split them into given and family names
+fn bibtex_name(author: &Author) -> String {
+    if author.is_organization() {
+        return format!("{{{}}}", escape(author.name.trim()));
+    }
+
+    // BibTeX's three part form is "von Last, Jr, First"
+    let name = author.personal_name();
+    let parts: Vec<&str> = [Some(name.family.as_str()), name.suffix.as_deref(), name.given.as_deref()]
+        .iter()
+        .flatten()
+        .copied()
+        .collect();
+
+    escape(&parts.join(", "))
+}
+
+// Escapes the characters LaTeX treats specially, text is otherwise left as UTF-8 for biber and inputenc
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\textbackslash{}"),
+            '{' | '}' | '&' | '%' | '$' | '#' | '_' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '~' => escaped.push_str("\\textasciitilde{}"),
+            '^' => escaped.push_str("\\textasciicircum{}"),
+            _ => escaped.push(c),
+        }
+    }
+
+    escaped
+}
@@ -0,0 +1,19 @@
+// https://openlibrary.org/works/OL45804W/bookshelves.json
+#[derive(Default, Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct Bookshelves {
+    pub want_to_read: usize,
+    pub currently_reading: usize,
+    pub already_read: usize,
+}
+
+impl Bookshelves {
+    pub fn total(&self) -> usize {
+        self.want_to_read + self.currently_reading + self.already_read
+    }
+}
+
+// The endpoint wraps the counts in a "counts" object
+#[derive(serde_derive::Deserialize)]
+pub(crate) struct BookshelvesResponse {
+    pub counts: Bookshelves,
+}
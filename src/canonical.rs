@@ -0,0 +1,131 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+
+// Open Library JSON with object keys sorted and no whitespace between tokens. A record read into
+// Edition, Work or Author and written back with to_canonical_json is byte for byte equal to the
+// canonicalized original, unknown fields included.
+pub fn to_canonical_json<T: Serialize>(value: &T) -> Result<String, serde_json::Error> {
+    serde_json::to_string(&serde_json::to_value(value)?)
+}
+
+pub fn canonicalize(json: &str) -> Result<String, serde_json::Error> {
+    let value: Value = serde_json::from_str(json)?;
+
+    serde_json::to_string(&value)
+}
+
+// For skip_serializing_if on fields that are filled with a default when absent
+pub(crate) fn is_default<T: Default + PartialEq>(value: &T) -> bool {
+    *value == T::default()
+}
+
+// The explicit nulls and empty values of a record as read, e.g. "notes": null or a description
+// with "type": "". The typed fields read them as None or a default, which is skipped when
+// writing, so they are put back wherever the written record has no value of its own.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct Elided {
+    values: Vec<ElidedValue>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct ElidedValue {
+    // JSON pointer of the member and its original value
+    pointer: String,
+    value: Value,
+    // Pointers and lengths of the arrays the member is nested in. Array elements are addressed by
+    // index, so once one of these arrays grows or shrinks the pointer may name another element.
+    arrays: Vec<(String, usize)>,
+}
+
+impl Elided {
+    fn collect(value: &Value) -> Self {
+        let mut elided = Self::default();
+        elided.walk(value, String::new(), &mut Vec::new());
+        elided
+    }
+
+    fn walk(&mut self, value: &Value, pointer: String, arrays: &mut Vec<(String, usize)>) {
+        match value {
+            Value::Object(object) => {
+                for (key, member) in object {
+                    let pointer = format!("{}/{}", pointer, key.replace('~', "~0").replace('/', "~1"));
+                    if is_empty(member) {
+                        self.values.push(ElidedValue {
+                            pointer,
+                            value: member.clone(),
+                            arrays: arrays.clone(),
+                        });
+                    } else {
+                        self.walk(member, pointer, arrays);
+                    }
+                }
+            }
+            Value::Array(elements) => {
+                arrays.push((pointer.clone(), elements.len()));
+                for (index, element) in elements.iter().enumerate() {
+                    self.walk(element, format!("{}/{}", pointer, index), arrays);
+                }
+                arrays.pop();
+            }
+            _ => {}
+        }
+    }
+
+    // Values inside an array whose length changed since reading are dropped rather than
+    // put back into whatever element now sits at their old index
+    fn restore(&self, record: &mut Value) {
+        for elided in self.values.iter() {
+            let unchanged = elided
+                .arrays
+                .iter()
+                .all(|(pointer, len)| record.pointer(pointer).and_then(Value::as_array).map(Vec::len) == Some(*len));
+            if !unchanged {
+                continue;
+            }
+
+            let (parent, key) = match elided.pointer.rsplit_once('/') {
+                Some(split) => split,
+                None => continue,
+            };
+            if let Some(Value::Object(parent)) = record.pointer_mut(parent) {
+                let key = key.replace("~1", "/").replace("~0", "~");
+                parent.entry(key).or_insert_with(|| elided.value.clone());
+            }
+        }
+    }
+}
+
+fn is_empty(value: &Value) -> bool {
+    match value {
+        Value::Null => true,
+        Value::String(string) => string.is_empty(),
+        Value::Array(elements) => elements.is_empty(),
+        Value::Object(object) => object.values().all(is_empty),
+        _ => false,
+    }
+}
+
+// Deserialize for the record types: `read` is the derived implementation
+pub(crate) fn read_record<'de, D, T, F>(deserializer: D, read: F) -> Result<(T, Elided), D::Error>
+where
+    D: Deserializer<'de>,
+    F: FnOnce(Value) -> Result<T, serde_json::Error>,
+{
+    let value = Value::deserialize(deserializer)?;
+    let elided = Elided::collect(&value);
+    let record = read(value).map_err(serde::de::Error::custom)?;
+
+    Ok((record, elided))
+}
+
+// Serialize for the record types: `write` is the derived implementation
+pub(crate) fn write_record<S, F>(serializer: S, elided: &Elided, write: F) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    F: FnOnce(serde_json::value::Serializer) -> Result<Value, serde_json::Error>,
+{
+    let mut value = write(serde_json::value::Serializer).map_err(serde::ser::Error::custom)?;
+    elided.restore(&mut value);
+
+    value.serialize(serializer)
+}
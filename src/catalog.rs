@@ -0,0 +1,363 @@
+use std::error;
+use std::fmt;
+use std::path::Path;
+use std::sync::Mutex;
+
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use surf::StatusCode;
+
+use crate::client::{first_key, process_olid_key, QueryType};
+use crate::dump::{self, Record};
+use crate::edition::Edition;
+use crate::work::Work;
+use crate::author::Author;
+use crate::isbn;
+use crate::language::LanguageRecord;
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS records (
+        key TEXT PRIMARY KEY,
+        type TEXT NOT NULL,
+        json TEXT NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS identifiers (
+        scheme TEXT NOT NULL,
+        value TEXT NOT NULL,
+        key TEXT NOT NULL,
+        PRIMARY KEY (scheme, value, key)
+    );
+    CREATE INDEX IF NOT EXISTS identifiers_key ON identifiers (key);
+";
+
+const TYPE_EDITION: &str = "/type/edition";
+const TYPE_WORK: &str = "/type/work";
+const TYPE_AUTHOR: &str = "/type/author";
+const TYPE_LANGUAGE: &str = "/type/language";
+const TYPE_REDIRECT: &str = "/type/redirect";
+const TYPE_DELETE: &str = "/type/delete";
+
+// Redirect chains in the dumps are short, this only guards against cycles
+const MAX_REDIRECTS: usize = 8;
+
+// Rows are committed in batches so importing a full dump does not hold one huge transaction
+const IMPORT_BATCH: usize = 10_000;
+
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scheme {
+    ISBN,
+    OCLC,
+    LCCN,
+    Goodreads,
+    LibraryThing,
+}
+
+impl Scheme {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Scheme::ISBN => "isbn",
+            Scheme::OCLC => "oclc",
+            Scheme::LCCN => "lccn",
+            Scheme::Goodreads => "goodreads",
+            Scheme::LibraryThing => "librarything",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum Error {
+    Sqlite(rusqlite::Error),
+    Json(serde_json::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Sqlite(err) => write!(f, "catalog database error: {}", err),
+            Error::Json(err) => write!(f, "catalog record is not valid JSON: {}", err),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Error::Sqlite(err) => Some(err),
+            Error::Json(err) => Some(err),
+        }
+    }
+}
+
+impl From<rusqlite::Error> for Error {
+    fn from(err: rusqlite::Error) -> Self {
+        Error::Sqlite(err)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(err: serde_json::Error) -> Self {
+        Error::Json(err)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Import {
+    pub records: usize,
+    pub errors: Vec<dump::Error>,
+}
+
+pub struct Catalog {
+    conn: Mutex<Connection>,
+}
+
+impl Catalog {
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        Self::init(Connection::open(path)?)
+    }
+
+    pub fn open_in_memory() -> Result<Self, Error> {
+        Self::init(Connection::open_in_memory()?)
+    }
+
+    fn init(conn: Connection) -> Result<Self, Error> {
+        conn.execute_batch(SCHEMA)?;
+
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    // Malformed dump rows are collected in the returned Import instead of aborting the whole run
+    pub fn import<I>(&self, records: I) -> Result<Import, Error>
+    where
+        I: IntoIterator<Item = Result<Record, dump::Error>>,
+    {
+        let mut conn = self.conn.lock().unwrap();
+        let mut import = Import::default();
+        let mut tx = conn.transaction()?;
+
+        for record in records {
+            match record {
+                Ok(record) => {
+                    insert_record(&tx, &record)?;
+                    import.records += 1;
+
+                    if import.records % IMPORT_BATCH == 0 {
+                        tx.commit()?;
+                        tx = conn.transaction()?;
+                    }
+                }
+                Err(err) => import.errors.push(err),
+            }
+        }
+        tx.commit()?;
+
+        Ok(import)
+    }
+
+    pub fn insert(&self, record: &Record) -> Result<(), Error> {
+        let conn = self.conn.lock().unwrap();
+
+        insert_record(&conn, record)
+    }
+
+    pub fn edition(&self, olid: &str) -> Result<Option<Edition>, Error> {
+        self.by_key(record_key("/books", olid), TYPE_EDITION)
+    }
+
+    pub fn work(&self, olid: &str) -> Result<Option<Work>, Error> {
+        self.by_key(record_key("/works", olid), TYPE_WORK)
+    }
+
+    pub fn author(&self, olid: &str) -> Result<Option<Author>, Error> {
+        self.by_key(record_key("/authors", olid), TYPE_AUTHOR)
+    }
+
+    // Language records are only present when the catalog was imported from the "all" dump
+    pub fn language(&self, code: &str) -> Result<Option<LanguageRecord>, Error> {
+        self.by_key(record_key("/languages", code), TYPE_LANGUAGE)
+    }
+
+    pub fn edition_by(&self, scheme: Scheme, value: &str) -> Result<Option<Edition>, Error> {
+        let key: Option<String> = {
+            let conn = self.conn.lock().unwrap();
+            conn.query_row(
+                "SELECT key FROM identifiers WHERE scheme = ?1 AND value = ?2 ORDER BY key LIMIT 1",
+                params![scheme.as_str(), normalize(scheme, value)],
+                |row| row.get(0),
+            )
+            .optional()?
+        };
+
+        match key {
+            Some(key) => self.by_key(key, TYPE_EDITION),
+            None => Ok(None),
+        }
+    }
+
+    pub fn edition_by_isbn(&self, isbn: &str) -> Result<Option<Edition>, Error> {
+        self.edition_by(Scheme::ISBN, isbn)
+    }
+
+    pub fn edition_by_oclc(&self, oclc: &str) -> Result<Option<Edition>, Error> {
+        self.edition_by(Scheme::OCLC, oclc)
+    }
+
+    pub fn edition_by_lccn(&self, lccn: &str) -> Result<Option<Edition>, Error> {
+        self.edition_by(Scheme::LCCN, lccn)
+    }
+
+    pub fn edition_by_goodreads(&self, id: &str) -> Result<Option<Edition>, Error> {
+        self.edition_by(Scheme::Goodreads, id)
+    }
+
+    pub fn edition_by_librarything(&self, id: &str) -> Result<Option<Edition>, Error> {
+        self.edition_by(Scheme::LibraryThing, id)
+    }
+
+    // The lookups behind Client::get_edition, get_work and get_author when a catalog is attached
+
+    pub(crate) fn get_edition(&self, query_type: QueryType) -> Result<Edition, surf::Error> {
+        let edition = match &query_type {
+            QueryType::OLID(key) => self.edition(key)?,
+            QueryType::ISBN(key) => self.edition_by_isbn(key)?,
+            QueryType::OCLC(key) => self.edition_by_oclc(key)?,
+            QueryType::LCCN(key) => self.edition_by_lccn(key)?,
+            QueryType::Goodreads(key) => self.edition_by_goodreads(key)?,
+            QueryType::LibraryThing(key) => self.edition_by_librarything(key)?,
+        };
+
+        edition.ok_or_else(|| not_found(&query_type))
+    }
+
+    pub(crate) fn get_work(&self, query_type: QueryType) -> Result<Work, surf::Error> {
+        let work = match &query_type {
+            QueryType::OLID(key) => self.work(key)?,
+            _ => {
+                let edition = self.get_edition(query_type)?;
+                let work_id = first_key(edition.get_works_ids(), "work", &edition.key)?;
+                return self.get_work(QueryType::OLID(work_id));
+            }
+        };
+
+        work.ok_or_else(|| not_found(&query_type))
+    }
+
+    pub(crate) fn get_author(&self, query_type: QueryType) -> Result<Author, surf::Error> {
+        let author = match &query_type {
+            QueryType::OLID(key) => self.author(key)?,
+            _ => {
+                let work = self.get_work(query_type)?;
+                let author_id = first_key(work.get_authors_ids(), "author", &work.key)?;
+                return self.get_author(QueryType::OLID(author_id));
+            }
+        };
+
+        author.ok_or_else(|| not_found(&query_type))
+    }
+
+    fn by_key<T: DeserializeOwned>(&self, mut key: String, type_key: &str) -> Result<Option<T>, Error> {
+        let conn = self.conn.lock().unwrap();
+
+        for _ in 0..MAX_REDIRECTS {
+            let row: Option<(String, String)> = conn
+                .query_row(
+                    "SELECT type, json FROM records WHERE key = ?1",
+                    params![key],
+                    |row| Ok((row.get(0)?, row.get(1)?)),
+                )
+                .optional()?;
+
+            match row {
+                Some((record_type, json)) if record_type == type_key => {
+                    return Ok(Some(serde_json::from_str(&json)?));
+                }
+                Some((record_type, json)) if record_type == TYPE_REDIRECT => {
+                    let redirect: Value = serde_json::from_str(&json)?;
+                    match redirect["location"].as_str() {
+                        Some(location) => key = String::from(location),
+                        None => return Ok(None),
+                    }
+                }
+                _ => return Ok(None),
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+// Records are stored under their full key, ids alone are not unique across types:
+// /people/admin and /usergroup/admin, or /languages/eng and an author key ending in eng
+fn insert_record(conn: &Connection, record: &Record) -> Result<(), Error> {
+    let key = record.key();
+
+    conn.execute("DELETE FROM identifiers WHERE key = ?1", params![key])?;
+
+    let (type_key, json) = match record {
+        Record::Edition(edition) => {
+            let third_party = edition.identifiers.clone().unwrap_or_default();
+            let identifiers = [
+                (Scheme::ISBN, &edition.isbn10),
+                (Scheme::ISBN, &edition.isbn13),
+                (Scheme::OCLC, &edition.oclc_numbers),
+                (Scheme::LCCN, &edition.lccn),
+                (Scheme::Goodreads, &third_party.goodreads),
+                (Scheme::LibraryThing, &third_party.librarything),
+            ];
+
+            for (scheme, values) in identifiers.iter() {
+                for value in values.iter().flatten() {
+                    // Both ISBN forms are indexed, so an edition with only an ISBN-10 is found by its ISBN-13 too
+                    let mut forms = vec![normalize(*scheme, value)];
+                    if *scheme == Scheme::ISBN {
+                        forms.extend(isbn::to_isbn10(value));
+                        forms.extend(isbn::to_isbn13(value));
+                    }
+
+                    for form in forms.iter() {
+                        conn.execute(
+                            "INSERT OR IGNORE INTO identifiers (scheme, value, key) VALUES (?1, ?2, ?3)",
+                            params![scheme.as_str(), form, key],
+                        )?;
+                    }
+                }
+            }
+
+            (TYPE_EDITION, serde_json::to_string(edition)?)
+        }
+        Record::Work(work) => (TYPE_WORK, serde_json::to_string(work)?),
+        Record::Author(author) => (TYPE_AUTHOR, serde_json::to_string(author)?),
+        Record::Other { type_key, .. } if type_key == TYPE_DELETE => {
+            conn.execute("DELETE FROM records WHERE key = ?1", params![key])?;
+            return Ok(());
+        }
+        Record::Other { type_key, json, .. } => (type_key.as_str(), serde_json::to_string(json)?),
+    };
+
+    conn.execute(
+        "INSERT OR REPLACE INTO records (key, type, json) VALUES (?1, ?2, ?3)",
+        params![key, type_key, json],
+    )?;
+
+    Ok(())
+}
+
+// ISBNs are stored without hyphens or spaces, LCCNs without spaces, so lookups match either spelling
+fn normalize(scheme: Scheme, value: &str) -> String {
+    match scheme {
+        Scheme::ISBN => isbn::normalize(value),
+        Scheme::OCLC | Scheme::LCCN | Scheme::Goodreads | Scheme::LibraryThing => value.chars().filter(|c| !c.is_whitespace()).collect(),
+    }
+}
+
+// Lookups take an id like OL1M or eng as well as a full key like /books/OL1M
+fn record_key(prefix: &str, id: &str) -> String {
+    format!("{}/{}", prefix, process_olid_key(id))
+}
+
+fn not_found(query_type: &QueryType) -> surf::Error {
+    surf::Error::from_str(StatusCode::NotFound, format!("{} is not in the offline catalog", query_type))
+}
@@ -1,67 +1,110 @@
-use std::{fmt::format, fs::File};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs::File;
 use std::io::Write;
-use surf::http::url;
 
 use async_std::task::block_on;
+use percent_encoding::{utf8_percent_encode, AsciiSet, CONTROLS};
+use surf::StatusCode;
 
-use serde_json::{Value};
-
-use crate::edition::Edition;
+use crate::edition::{BibkeyRecord, Edition, IdentifierSearch};
 use crate::work::Work;
 use crate::author::Author;
 use crate::entity::Entity;
-
-pub enum Paths {
-    Works,
-    Authors,
-    Books,
-    ISBN,
-}
-
+use crate::catalog::Catalog;
+use crate::ratings::Ratings;
+use crate::bookshelves::{Bookshelves, BookshelvesResponse};
+use crate::list::{list_path, List, ListSubjects, ListSummary, NewList, NewListResponse, Page, Paging, Seed, SeedChanges, SeedEntry};
+use crate::reading_log::{ReadingLog, Shelf};
+use crate::session::{with_comment, Credentials, KeysBody, PasswordForm, Session};
+use crate::conflict::{latest_revision, revision, Conflict, SaveError};
+use crate::read::{Availability, ReadKey};
+use crate::archive::{Archive, ArchiveItem};
+use crate::language::LanguageRecord;
+use crate::marc::{RawMarc, SourceRecord};
+use crate::covers::{CoverKey, CoverSize};
+
+#[allow(clippy::upper_case_acronyms)]
 pub enum QueryType {
     OLID(String),
     ISBN(String),
-}
-
-pub enum CoverSize {
-    S,
-    M,
-    L,
-}
-
-pub enum CoverKey {
-    ISBN(String),
     OCLC(String),
     LCCN(String),
-    OLID(String),
-    ID(String),
+    Goodreads(String),
+    LibraryThing(String),
+}
+
+impl fmt::Display for QueryType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            QueryType::OLID(key) => write!(f, "OLID {}", key),
+            QueryType::ISBN(key) => write!(f, "ISBN {}", key),
+            QueryType::OCLC(key) => write!(f, "OCLC {}", key),
+            QueryType::LCCN(key) => write!(f, "LCCN {}", key),
+            QueryType::Goodreads(key) => write!(f, "Goodreads ID {}", key),
+            QueryType::LibraryThing(key) => write!(f, "LibraryThing ID {}", key),
+        }
+    }
 }
 
 pub struct Client {
     base_url: String,
+    catalog: Option<Catalog>,
+    session: Option<Session>,
+    archive: Archive,
+}
+
+impl Default for Client {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Client {
     pub fn new() -> Self {
-        return Self{
+        Self {
             base_url: String::from("https://openlibrary.org"),
-        };
+            catalog: None,
+            session: None,
+            archive: Archive::default(),
+        }
+    }
+
+    // Points the client at another Open Library instance, e.g. a local mock server
+    pub fn with_base_url(base_url: &str) -> Self {
+        Self {
+            base_url: String::from(base_url.trim_end_matches('/')),
+            ..Self::new()
+        }
+    }
+
+    // Answers get_edition, get_work, get_author and entity_by_isbn from a local catalog
+    // instead of openlibrary.org
+    pub fn with_catalog(catalog: Catalog) -> Self {
+        Self {
+            catalog: Some(catalog),
+            ..Self::new()
+        }
     }
 
     pub async fn get_edition(&self, query_type: QueryType) -> Result<Edition, surf::Error> {
+        if let Some(catalog) = &self.catalog {
+            return catalog.get_edition(query_type);
+        }
+
         match query_type {
             QueryType::OLID(key) => {
                 let surf_client = surf::client().with(surf::middleware::Redirect::default());
-                let uri = compose_ol_uri(QueryType::OLID(key));
+                let uri = compose_ol_uri(&self.base_url, &QueryType::OLID(key));
                 let req = surf_client.get(uri);
 
                 let edition_json: Edition = block_on(surf_client.recv_json(req))?;
                 
                 Ok(edition_json)
             }
-            QueryType::ISBN(key) => {
+            other => {
                 let surf_client = surf::client().with(surf::middleware::Redirect::default());
-                let uri = compose_ol_uri(QueryType::ISBN(key));
+                let uri = self.edition_uri(other).await?;
                 let req = surf_client.get(uri);
 
                 let edition_json: Edition = block_on(surf_client.recv_json(req))?;
@@ -72,31 +115,35 @@ impl Client {
     }
 
     pub async fn get_author(&self, query_type: QueryType) -> Result<Author, surf::Error> {
+        if let Some(catalog) = &self.catalog {
+            return catalog.get_author(query_type);
+        }
+
         match query_type {
             QueryType::OLID(key) => {
                 let surf_client = surf::client().with(surf::middleware::Redirect::default());
-                let author_uri = compose_ol_uri(QueryType::OLID(key));
+                let author_uri = compose_ol_uri(&self.base_url, &QueryType::OLID(key));
                 let author_uri_req = surf_client.get(author_uri);
                 
                 let author_json: Author = surf_client.recv_json(author_uri_req).await?;
                 
                 Ok(author_json)
             }
-            QueryType::ISBN(key) => {
+            other => {
                 let surf_client = surf::client().with(surf::middleware::Redirect::default());
-                let uri = compose_ol_uri(QueryType::ISBN(key));
+                let uri = self.edition_uri(other).await?;
                 let req = surf_client.get(uri);
 
                 let edition_json: Edition = block_on(surf_client.recv_json(req))?;
-                let work_ids = edition_json.get_works_ids();
+                let work_id = first_key(edition_json.get_works_ids(), "work", &edition_json.key)?;
 
-                let work_uri = compose_ol_uri(QueryType::OLID(work_ids[0].clone()));
+                let work_uri = compose_ol_uri(&self.base_url, &QueryType::OLID(work_id));
                 let work_uri_req = surf_client.get(work_uri);
 
                 let work_json: Work = block_on(surf_client.recv_json(work_uri_req))?;
-                let author_ids = work_json.get_authors_ids();
+                let author_id = first_key(work_json.get_authors_ids(), "author", &work_json.key)?;
 
-                let author_uri = compose_ol_uri(QueryType::OLID(author_ids[0].clone()));
+                let author_uri = compose_ol_uri(&self.base_url, &QueryType::OLID(author_id));
                 let author_uri_req = surf_client.get(author_uri);
                 
                 let author_json: Author = surf_client.recv_json(author_uri_req).await?;
@@ -107,25 +154,29 @@ impl Client {
     }
 
     pub async fn get_work(&self, query_type: QueryType) -> Result<Work, surf::Error> {
+        if let Some(catalog) = &self.catalog {
+            return catalog.get_work(query_type);
+        }
+
         match query_type {
             QueryType::OLID(key) => {
                 let surf_client = surf::client().with(surf::middleware::Redirect::default());
-                let work_uri = compose_ol_uri(QueryType::OLID(key));
+                let work_uri = compose_ol_uri(&self.base_url, &QueryType::OLID(key));
                 let work_uri_req = surf_client.get(work_uri);
 
                 let work_json: Work = surf_client.recv_json(work_uri_req).await?;
 
                 Ok(work_json)
             }
-            QueryType::ISBN(key) => {
+            other => {
                 let surf_client = surf::client().with(surf::middleware::Redirect::default());
-                let uri = compose_ol_uri(QueryType::ISBN(key));
+                let uri = self.edition_uri(other).await?;
                 let req = surf_client.get(uri);
 
                 let edition_json: Edition = block_on(surf_client.recv_json(req))?;
-                let work_ids = edition_json.get_works_ids();
+                let work_id = first_key(edition_json.get_works_ids(), "work", &edition_json.key)?;
 
-                let work_uri = compose_ol_uri(QueryType::OLID(work_ids[0].clone()));
+                let work_uri = compose_ol_uri(&self.base_url, &QueryType::OLID(work_id));
                 let work_uri_req = surf_client.get(work_uri);
 
                 let work_json: Work = surf_client.recv_json(work_uri_req).await?;
@@ -135,86 +186,457 @@ impl Client {
         }
     }
 
+    // code is a MARC language code like "eng" or a key like "/languages/eng"
+    pub async fn get_language(&self, code: &str) -> Result<LanguageRecord, surf::Error> {
+        let code = code.trim_start_matches("/languages/");
+
+        if let Some(catalog) = &self.catalog {
+            return catalog
+                .language(code)?
+                .ok_or_else(|| surf::Error::from_str(StatusCode::NotFound, format!("language {} is not in the offline catalog", code)));
+        }
+
+        let surf_client = surf::client().with(surf::middleware::Redirect::default());
+        let uri = format!("{}/languages/{}.json", self.base_url, code);
+        let req = surf_client.get(uri);
+
+        let language_json: LanguageRecord = surf_client.recv_json(req).await?;
+
+        Ok(language_json)
+    }
+
     pub async fn save_cover(&self, cover_size: CoverSize, path: String, cover_key: CoverKey) -> Result<(), surf::Error>{
         let surf_client = surf::client().with(surf::middleware::Redirect::default());
 
         match cover_key {
-            CoverKey::ISBN(isbn) => {
-                let uri = construct_cover_uri(cover_size, &isbn);
+            CoverKey::ISBN(_) => {
+                let uri = cover_key.url(cover_size);
                 let req = surf::get(uri);
                 let cover_image = surf_client.recv_bytes(req).await?;
-                assert!(cover_image.len() > 0);
+                assert!(!cover_image.is_empty());
 
-                let path_str = format!("{}", path);
-                let mut cover_file = File::create(path_str)?;
+                let mut cover_file = File::create(path)?;
                 cover_file.write_all(&cover_image)?;
             }
-            CoverKey::LCCN(isbn) => {}
-            CoverKey::OCLC(isbn) => {}
-            CoverKey::OLID(isbn) => {}
-            CoverKey::ID(isbn) => {}           
+            CoverKey::LCCN(_) => {}
+            CoverKey::OCLC(_) => {}
+            CoverKey::OLID(_) => {}
+            CoverKey::ID(_) => {}
         }
         
         Ok(())
     }
 
-    pub async fn entity_by_isbn(self, isbn: &str) -> Result<Entity, surf::Error> {
-        let edition_json: Edition = block_on(self.get_edition(QueryType::ISBN(String::from(isbn))))?;
-        let work_ids = edition_json.get_works_ids();
+    pub async fn entity_by_isbn(&self, isbn: &str) -> Result<Entity, surf::Error> {
+        let edition_json: Edition = self.get_edition(QueryType::ISBN(String::from(isbn))).await?;
+        let work_id = first_key(edition_json.get_works_ids(), "work", &edition_json.key)?;
 
-        let work_json: Work = block_on(self.get_work(QueryType::OLID(work_ids[0].clone())))?;
+        // A work without authors is valid, the entity then has none
+        let work_json: Work = self.get_work(QueryType::OLID(work_id)).await?;
         let author_ids = work_json.get_authors_ids();
-        
-        let author_json: Author = block_on(self.get_author(QueryType::OLID(author_ids[0].clone())))?;
 
-        let entity: Entity = Entity::new(process_olid_key(&edition_json.key), edition_json, work_json, author_json);
+        let mut authors_json: Vec<Author> = Vec::new();
+        for author_id in author_ids {
+            authors_json.push(self.get_author(QueryType::OLID(author_id)).await?);
+        }
+
+        let entity: Entity = Entity::with_authors(process_olid_key(&edition_json.key), edition_json, work_json, authors_json);
 
         Ok(entity)
     }
 
+    pub async fn work_ratings(&self, olid: &str) -> Result<Ratings, surf::Error> {
+        let surf_client = surf::client().with(surf::middleware::Redirect::default());
+        let uri = format!("{}/works/{}/ratings.json", self.base_url, process_olid_key(olid));
+        let req = surf_client.get(uri);
+
+        let ratings_json: Ratings = surf_client.recv_json(req).await?;
+
+        Ok(ratings_json)
+    }
+
+    pub async fn work_bookshelves(&self, olid: &str) -> Result<Bookshelves, surf::Error> {
+        let surf_client = surf::client().with(surf::middleware::Redirect::default());
+        let uri = format!("{}/works/{}/bookshelves.json", self.base_url, process_olid_key(olid));
+        let req = surf_client.get(uri);
+
+        let bookshelves_json: BookshelvesResponse = surf_client.recv_json(req).await?;
+
+        Ok(bookshelves_json.counts)
+    }
+
+    pub async fn user_lists(&self, username: &str, paging: Paging) -> Result<Page<ListSummary>, surf::Error> {
+        let uri = format!("{}/people/{}/lists.json?{}", self.base_url, encode_segment(username), paging.query());
+
+        self.recv_json(uri).await
+    }
+
+    pub async fn list(&self, list_key: &str) -> Result<List, surf::Error> {
+        let uri = format!("{}{}.json", self.base_url, list_path(list_key));
+
+        self.recv_json(uri).await
+    }
+
+    pub async fn list_seeds(&self, list_key: &str, paging: Paging) -> Result<Page<SeedEntry>, surf::Error> {
+        let uri = format!("{}{}/seeds.json?{}", self.base_url, list_path(list_key), paging.query());
+
+        self.recv_json(uri).await
+    }
+
+    pub async fn list_editions(&self, list_key: &str, paging: Paging) -> Result<Page<Edition>, surf::Error> {
+        let uri = format!("{}{}/editions.json?{}", self.base_url, list_path(list_key), paging.query());
+
+        self.recv_json(uri).await
+    }
+
+    pub async fn list_subjects(&self, list_key: &str) -> Result<ListSubjects, surf::Error> {
+        let uri = format!("{}{}/subjects.json", self.base_url, list_path(list_key));
+
+        self.recv_json(uri).await
+    }
+
+    pub async fn login(&mut self, credentials: Credentials) -> Result<(), surf::Error> {
+        // No redirect middleware here, the session cookie is set on the redirect response itself
+        let surf_client = surf::client();
+        let uri = format!("{}/account/login", self.base_url);
+
+        let req = match &credentials {
+            Credentials::Password { username, password } => {
+                let form = PasswordForm { username, password, remember: true };
+                surf_client.post(uri).body(surf::Body::from_form(&form)?)
+            }
+            Credentials::Keys { access, secret } => {
+                let keys = KeysBody { access, secret };
+                surf_client.post(uri).body(surf::Body::from_json(&keys)?)
+            }
+        };
+
+        let res = req.await?;
+        if res.status().is_client_error() || res.status().is_server_error() {
+            return Err(surf::Error::from_str(res.status(), "login failed"));
+        }
+
+        self.session = Some(Session::from_response(&res)?);
+
+        Ok(())
+    }
+
+    pub fn set_session(&mut self, session: Session) {
+        self.session = Some(session);
+    }
+
+    pub fn session(&self) -> Option<&Session> {
+        self.session.as_ref()
+    }
+
+    pub fn logout(&mut self) {
+        self.session = None;
+    }
 
+    pub fn set_archive(&mut self, archive: Archive) {
+        self.archive = archive;
+    }
+
+    pub fn archive(&self) -> &Archive {
+        &self.archive
+    }
+
+    // Item metadata for an Edition.ocaid: files, formats and access restrictions
+    pub async fn ia_metadata(&self, ocaid: &str) -> Result<ArchiveItem, surf::Error> {
+        self.recv_json(self.archive.metadata_url(ocaid)).await
+    }
+
+    // Fetches the original catalog record behind one of Edition.source_records from the Internet Archive
+    pub async fn source_record_marc(&self, source: &str) -> Result<RawMarc, surf::Error> {
+        let source_record = SourceRecord::parse(source)
+            .ok_or_else(|| surf::Error::from_str(StatusCode::BadRequest, format!("{} is not a MARC source record", source)))?;
+        let surf_client = surf::client().with(surf::middleware::Redirect::default());
+
+        match source_record {
+            SourceRecord::Marc { item, file, range } => {
+                let mut req = surf_client.get(self.archive.download_url(&item, Some(&file)));
+                if let Some((offset, length)) = range {
+                    req = req.header("Range", format!("bytes={}-{}", offset, offset + length.max(1) - 1));
+                }
+
+                let mut res = req.await?;
+                if !res.status().is_success() {
+                    return Err(surf::Error::from_str(res.status(), format!("{} could not be fetched", source)));
+                }
+                let bytes = res.body_bytes().await?;
+
+                // A server that ignores Range sends the whole file
+                match range {
+                    Some((offset, length)) if res.status() == StatusCode::Ok && bytes.len() as u64 > length => {
+                        let start = offset as usize;
+                        let end = (offset + length) as usize;
+                        let record = bytes
+                            .get(start..end)
+                            .ok_or_else(|| surf::Error::from_str(StatusCode::RequestedRangeNotSatisfiable, format!("{} is past the end of {}", source, file)))?;
+                        Ok(RawMarc::Marc21(record.to_vec()))
+                    }
+                    _ => Ok(RawMarc::Marc21(bytes)),
+                }
+            }
+            SourceRecord::Ia(ocaid) => {
+                let xml_uri = self.archive.download_url(&ocaid, Some(&format!("{}_marc.xml", ocaid)));
+                let mut res = surf_client.get(xml_uri).await?;
+                if res.status().is_success() {
+                    return Ok(RawMarc::MarcXml(res.body_string().await?));
+                }
+
+                let binary_uri = self.archive.download_url(&ocaid, Some(&format!("{}_meta.mrc", ocaid)));
+                let mut res = surf_client.get(binary_uri).await?;
+                if !res.status().is_success() {
+                    return Err(surf::Error::from_str(res.status(), format!("{} has no MARC record", ocaid)));
+                }
+
+                Ok(RawMarc::Marc21(res.body_bytes().await?))
+            }
+        }
+    }
+
+    // The save methods refuse to overwrite newer revisions: if the server's latest_revision is
+    // past the revision the record was loaded at, a SaveError::Conflict is returned instead
+    pub async fn save_edition(&self, edition: &Edition, comment: &str) -> Result<(), SaveError<Edition>> {
+        self.save_record(&edition.key, edition, comment).await
+    }
+
+    pub async fn save_work(&self, work: &Work, comment: &str) -> Result<(), SaveError<Work>> {
+        self.save_record(&work.key, work, comment).await
+    }
+
+    pub async fn save_author(&self, author: &Author, comment: &str) -> Result<(), SaveError<Author>> {
+        self.save_record(&author.key, author, comment).await
+    }
+
+    async fn save_record<T>(&self, key: &str, record: &T, comment: &str) -> Result<(), SaveError<T>>
+    where
+        T: serde::Serialize + serde::de::DeserializeOwned + Clone,
+    {
+        self.require_session()?;
+
+        let loaded = revision(&serde_json::to_value(record)?);
+        if let Some(theirs) = self.fetch_record_json(key, None).await? {
+            if latest_revision(&theirs) > loaded {
+                // A record without a revision was never loaded from the server, there is no base
+                let base = match loaded {
+                    0 => None,
+                    _ => self.fetch_record_json(key, Some(loaded)).await?.and_then(|base| serde_json::from_value(base).ok()),
+                };
+
+                return Err(SaveError::Conflict(Box::new(Conflict {
+                    base,
+                    ours: record.clone(),
+                    theirs: serde_json::from_value(theirs)?,
+                })));
+            }
+        }
+
+        let surf_client = surf::client();
+        let uri = format!("{}{}.json", self.base_url, key);
+        let req = surf_client
+            .put(uri)
+            .body(surf::Body::from_json(&with_comment(record, comment)?)?);
+
+        self.send_authed(req, &format!("saving {}", key)).await?;
+
+        Ok(())
+    }
+
+    // Creates a list owned by the logged in user and returns its key, e.g. /people/george08/lists/OL97L
+    pub async fn create_list(&self, name: &str, description: &str, seeds: &[Seed]) -> Result<String, surf::Error> {
+        let username = self.require_username()?;
+
+        let surf_client = surf::client();
+        let uri = format!("{}/people/{}/lists.json", self.base_url, username);
+        let list = NewList { name, description, tags: Vec::new(), seeds };
+        let req = surf_client.post(uri).body(surf::Body::from_json(&list)?);
+
+        let mut res = self.send_authed(req, "creating list").await?;
+        let created: NewListResponse = res.body_json().await?;
+
+        Ok(created.key)
+    }
+
+    pub async fn add_seeds(&self, list_key: &str, seeds: &[Seed]) -> Result<(), surf::Error> {
+        self.change_seeds(list_key, &SeedChanges { add: seeds, remove: &[] }).await
+    }
+
+    pub async fn remove_seeds(&self, list_key: &str, seeds: &[Seed]) -> Result<(), surf::Error> {
+        self.change_seeds(list_key, &SeedChanges { add: &[], remove: seeds }).await
+    }
+
+    async fn change_seeds(&self, list_key: &str, changes: &SeedChanges<'_>) -> Result<(), surf::Error> {
+        let surf_client = surf::client();
+        let uri = format!("{}{}/seeds.json", self.base_url, list_path(list_key));
+        let req = surf_client.post(uri).body(surf::Body::from_json(changes)?);
+
+        self.send_authed(req, &format!("updating seeds of {}", list_key)).await?;
+
+        Ok(())
+    }
+
+    pub async fn delete_list(&self, list_key: &str) -> Result<(), surf::Error> {
+        let surf_client = surf::client();
+        let uri = format!("{}{}/delete.json", self.base_url, list_path(list_key));
+        let req = surf_client.post(uri);
+
+        self.send_authed(req, &format!("deleting {}", list_key)).await?;
+
+        Ok(())
+    }
+
+    fn require_session(&self) -> Result<&Session, surf::Error> {
+        self.session
+            .as_ref()
+            .ok_or_else(|| surf::Error::from_str(StatusCode::Unauthorized, "this request requires a login"))
+    }
+
+    fn require_username(&self) -> Result<String, surf::Error> {
+        self.require_session()?
+            .username()
+            .ok_or_else(|| surf::Error::from_str(StatusCode::Unauthorized, "session cookie does not name a user"))
+    }
+
+    // Sends a request with the session cookie and turns error statuses into errors
+    async fn send_authed(&self, req: surf::RequestBuilder, action: &str) -> Result<surf::Response, surf::Error> {
+        let session = self.require_session()?;
+
+        let mut res = req.header("Cookie", session.cookie()).await?;
+        if !res.status().is_success() {
+            let status = res.status();
+            let body = res.body_string().await.unwrap_or_default();
+            let message = if body.is_empty() { status.canonical_reason().to_string() } else { body };
+            return Err(surf::Error::from_str(status, format!("{} failed: {}", action, message)));
+        }
+
+        Ok(res)
+    }
+
+    // Fetches the raw JSON of a record, optionally at an older revision. None if it does not exist yet.
+    async fn fetch_record_json(&self, key: &str, revision: Option<u64>) -> Result<Option<serde_json::Value>, surf::Error> {
+        let surf_client = surf::client().with(surf::middleware::Redirect::default());
+        let uri = match revision {
+            Some(revision) => format!("{}{}.json?v={}", self.base_url, key, revision),
+            None => format!("{}{}.json", self.base_url, key),
+        };
+
+        let mut res = surf_client.get(uri).await?;
+        if res.status() == StatusCode::NotFound {
+            return Ok(None);
+        }
+        if !res.status().is_success() {
+            return Err(surf::Error::from_str(res.status(), format!("fetching {} failed", key)));
+        }
+
+        Ok(Some(res.body_json().await?))
+    }
+
+    // Results are keyed by ReadKey::request_id. A single key uses /api/volumes/brief/{kind}/{id}.json,
+    // several keys are batched into one /api/volumes/brief/json/{kind}:{id}|... request
+    pub async fn read_availability(&self, keys: &[ReadKey]) -> Result<BTreeMap<String, Availability>, surf::Error> {
+        match keys {
+            [] => Ok(BTreeMap::new()),
+            [key] => {
+                let uri = format!("{}/api/volumes/brief/{}/{}.json", self.base_url, key.kind(), encode_segment(key.value()));
+                let availability: Availability = self.recv_json(uri).await?;
+
+                let mut availabilities = BTreeMap::new();
+                availabilities.insert(key.request_id(), availability);
+
+                Ok(availabilities)
+            }
+            keys => {
+                let ids: Vec<String> = keys.iter().map(|key| format!("{}:{}", key.kind(), encode_segment(key.value()))).collect();
+                let uri = format!("{}/api/volumes/brief/json/{}", self.base_url, ids.join("|"));
+
+                self.recv_json(uri).await
+            }
+        }
+    }
+
+    // Pages start at 1
+    pub async fn reading_log(&self, username: &str, shelf: Shelf, page: usize) -> Result<ReadingLog, surf::Error> {
+        let uri = format!("{}/people/{}/books/{}.json?page={}", self.base_url, encode_segment(username), shelf.path(), page.max(1));
+
+        self.recv_json(uri).await
+    }
+
+    // OLID and ISBN have their own paths, the other identifiers are first resolved to an edition key
+    async fn edition_uri(&self, query_type: QueryType) -> Result<String, surf::Error> {
+        let not_found = || surf::Error::from_str(StatusCode::NotFound, format!("no edition found for {}", query_type));
+
+        let key = match &query_type {
+            QueryType::OLID(_) | QueryType::ISBN(_) => return Ok(compose_ol_uri(&self.base_url, &query_type)),
+            QueryType::OCLC(_) | QueryType::LCCN(_) => {
+                let records: BTreeMap<String, BibkeyRecord> = self.recv_json(compose_ol_uri(&self.base_url, &query_type)).await?;
+                records.into_iter().next().map(|(_, record)| record.key).ok_or_else(not_found)?
+            }
+            QueryType::Goodreads(_) | QueryType::LibraryThing(_) => {
+                let search: IdentifierSearch = self.recv_json(compose_ol_uri(&self.base_url, &query_type)).await?;
+                search
+                    .docs
+                    .into_iter()
+                    .find_map(|doc| doc.editions.and_then(|editions| editions.docs.into_iter().next()))
+                    .map(|edition| edition.key)
+                    .ok_or_else(not_found)?
+            }
+        };
+
+        Ok(compose_ol_uri(&self.base_url, &QueryType::OLID(key)))
+    }
+
+    async fn recv_json<T: serde::de::DeserializeOwned>(&self, uri: String) -> Result<T, surf::Error> {
+        let surf_client = surf::client().with(surf::middleware::Redirect::default());
+        let req = surf_client.get(uri);
+
+        surf_client.recv_json(req).await
+    }
 }
 
-fn process_olid_key(json_olid: &str) -> String {
+pub(crate) fn process_olid_key(json_olid: &str) -> String {
     let index = json_olid.rfind('/');
 
-    let olid = match index {
+    match index {
         Some(index) => {
-            let (book, s_olid) = json_olid.split_at(index + 1);
+            let (_, s_olid) = json_olid.split_at(index + 1);
             String::from(s_olid)
         }
         None => String::from(json_olid),
-    };
+    }
+}
+
+// Editions without works and works without authors are valid records, following one fails with 404
+pub(crate) fn first_key(keys: Vec<String>, kind: &str, key: &str) -> Result<String, surf::Error> {
+    keys.into_iter()
+        .next()
+        .map(|first| process_olid_key(&first))
+        .ok_or_else(|| surf::Error::from_str(StatusCode::NotFound, format!("{} has no {}", key, kind)))
+}
+
+// Everything that could end or split a path segment, on top of controls and non-ASCII
+const PATH_SEGMENT: &AsciiSet = &CONTROLS
+    .add(b' ').add(b'"').add(b'#').add(b'%').add(b'/').add(b'<').add(b'>').add(b'?')
+    .add(b'[').add(b'\\').add(b']').add(b'^').add(b'`').add(b'{').add(b'|').add(b'}');
 
-    return olid;
+// Usernames and identifiers come from callers and are escaped before going into a path
+fn encode_segment(segment: &str) -> String {
+    utf8_percent_encode(segment, PATH_SEGMENT).to_string()
 }
 
-fn compose_ol_uri (query_type: QueryType) -> String {
-    let base_url = String::from("https://openlibrary.org");
+// OCLC and LCCN go through the Books API, Goodreads and LibraryThing IDs through search,
+// both answer with the key of the matching edition
+fn compose_ol_uri (base_url: &str, query_type: &QueryType) -> String {
     let url_end = String::from(".json");
 
-    let uri = match query_type {
-        QueryType::OLID(key) => format!("{}/{}/{}{}", base_url, "q", process_olid_key(&key), url_end),
+    match query_type {
+        QueryType::OLID(key) => format!("{}/{}/{}{}", base_url, "q", process_olid_key(key), url_end),
         QueryType::ISBN(key) => format!("{}/{}/{}{}", base_url, "isbn", key, url_end),
-    };
-
-    return uri;
+        QueryType::OCLC(key) => format!("{}/api/books?bibkeys=OCLC:{}&format=json&jscmd=data", base_url, key),
+        QueryType::LCCN(key) => format!("{}/api/books?bibkeys=LCCN:{}&format=json&jscmd=data", base_url, key),
+        QueryType::Goodreads(key) => format!("{}/search.json?q=id_goodreads:{}&fields=key,editions", base_url, key),
+        QueryType::LibraryThing(key) => format!("{}/search.json?q=id_librarything:{}&fields=key,editions", base_url, key),
+    }
 }
 
-// https://covers.openlibrary.org/b/isbn/9781849352826-L.jpg
-// https://covers.openlibrary.org/b/$key/$value-$size.jpg
-fn construct_cover_uri(cover_size: CoverSize, isbn: &str) -> String {
-    let cover_size = match cover_size {
-        CoverSize::L => "L",
-        CoverSize::M => "M",
-        CoverSize::S => "S",
-    };
-    
-    let cover_url = String::from("https://covers.openlibrary.org/b");
-    let cover_path = "/isbn/";
-    let cover_end = ".jpg";
-
-    let uri = format!("{}{}{}-{}{}", cover_url, cover_path, isbn, cover_size, cover_end);
-
-    return uri;
-}
@@ -0,0 +1,123 @@
+use std::collections::BTreeSet;
+use std::error;
+use std::fmt;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+// Maintained by the server, a merge always keeps the latest values
+const BOOKKEEPING_FIELDS: [&str; 5] = ["key", "revision", "latest_revision", "created", "last_modified"];
+
+// Raised by the save methods when the record changed on the server since it was loaded
+#[derive(Debug, Clone, PartialEq)]
+pub struct Conflict<T> {
+    // The revision the edit started from, None if it could not be fetched
+    pub base: Option<T>,
+    pub ours: T,
+    pub theirs: T,
+}
+
+impl<T: Serialize + DeserializeOwned> Conflict<T> {
+    // Three-way merge per top-level field: a field changed on one side only takes that
+    // side, a field changed identically on both sides is kept, anything else is reported.
+    // The merged record carries the server revision so it can be saved again.
+    pub fn merge(&self) -> Result<T, MergeConflict> {
+        let base = match &self.base {
+            Some(base) => to_object(base)?,
+            None => Map::new(),
+        };
+        let ours = to_object(&self.ours)?;
+        let theirs = to_object(&self.theirs)?;
+
+        let fields: BTreeSet<&String> = base.keys().chain(ours.keys()).chain(theirs.keys()).collect();
+
+        let mut merged = Map::new();
+        let mut conflicts = Vec::new();
+
+        for field in fields {
+            let (b, o, t) = (base.get(field), ours.get(field), theirs.get(field));
+
+            let value = if BOOKKEEPING_FIELDS.contains(&field.as_str()) || o == t || o == b {
+                t
+            } else if t == b {
+                o
+            } else {
+                conflicts.push(field.clone());
+                continue;
+            };
+
+            if let Some(value) = value {
+                merged.insert(field.clone(), value.clone());
+            }
+        }
+
+        if !conflicts.is_empty() {
+            return Err(MergeConflict { fields: conflicts });
+        }
+
+        serde_json::from_value(Value::Object(merged)).map_err(|err| MergeConflict {
+            fields: vec![format!("merged record is invalid: {}", err)],
+        })
+    }
+}
+
+fn to_object<T: Serialize>(record: &T) -> Result<Map<String, Value>, MergeConflict> {
+    match serde_json::to_value(record) {
+        Ok(Value::Object(object)) => Ok(object),
+        _ => Err(MergeConflict {
+            fields: vec![String::from("record does not serialize to a JSON object")],
+        }),
+    }
+}
+
+// The fields both sides changed in different ways
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MergeConflict {
+    pub fields: Vec<String>,
+}
+
+impl fmt::Display for MergeConflict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "conflicting changes to {}", self.fields.join(", "))
+    }
+}
+
+impl error::Error for MergeConflict {}
+
+#[derive(Debug)]
+pub enum SaveError<T> {
+    Conflict(Box<Conflict<T>>),
+    Http(surf::Error),
+}
+
+impl<T> fmt::Display for SaveError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SaveError::Conflict(_) => write!(f, "record was changed on the server since it was loaded"),
+            SaveError::Http(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl<T: fmt::Debug> error::Error for SaveError<T> {}
+
+impl<T> From<surf::Error> for SaveError<T> {
+    fn from(err: surf::Error) -> Self {
+        SaveError::Http(err)
+    }
+}
+
+impl<T> From<serde_json::Error> for SaveError<T> {
+    fn from(err: serde_json::Error) -> Self {
+        SaveError::Http(surf::Error::from(err))
+    }
+}
+
+pub(crate) fn revision(record: &Value) -> u64 {
+    record["revision"].as_u64().unwrap_or(0)
+}
+
+pub(crate) fn latest_revision(record: &Value) -> u64 {
+    record["latest_revision"].as_u64().unwrap_or_else(|| revision(record))
+}
@@ -0,0 +1,55 @@
+use crate::edition::Edition;
+
+pub enum CoverSize {
+    S,
+    M,
+    L,
+}
+
+#[allow(clippy::upper_case_acronyms)]
+pub enum CoverKey {
+    ISBN(String),
+    OCLC(String),
+    LCCN(String),
+    OLID(String),
+    ID(String),
+}
+
+impl CoverKey {
+    // https://covers.openlibrary.org/b/isbn/9781849352826-L.jpg
+    // https://covers.openlibrary.org/b/$key/$value-$size.jpg
+    pub fn url(&self, cover_size: CoverSize) -> String {
+        let cover_size = match cover_size {
+            CoverSize::L => "L",
+            CoverSize::M => "M",
+            CoverSize::S => "S",
+        };
+
+        let (key, value) = match self {
+            CoverKey::ISBN(value) => ("isbn", value),
+            CoverKey::OCLC(value) => ("oclc", value),
+            CoverKey::LCCN(value) => ("lccn", value),
+            CoverKey::OLID(value) => ("olid", value),
+            CoverKey::ID(value) => ("id", value),
+        };
+
+        format!("https://covers.openlibrary.org/b/{}/{}-{}.jpg", key, value, cover_size)
+    }
+}
+
+impl Edition {
+    // The edition's own cover when it has one, the cover Open Library serves for its ISBN otherwise
+    pub fn cover_url(&self, cover_size: CoverSize) -> Option<String> {
+        let cover_id = self.covers.iter().flatten().find(|id| **id > 0);
+        if let Some(id) = cover_id {
+            return Some(CoverKey::ID(id.to_string()).url(cover_size));
+        }
+
+        self.isbn13
+            .iter()
+            .chain(self.isbn10.iter())
+            .flatten()
+            .next()
+            .map(|isbn| CoverKey::ISBN(isbn.clone()).url(cover_size))
+    }
+}
@@ -0,0 +1,382 @@
+use crate::author::Author;
+use crate::date::PartialDate;
+use crate::entity::Entity;
+use crate::name::initials;
+
+// A CSL-JSON item as read by citeproc processors, Zotero and pandoc
+#[derive(Default, Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct Item {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub type_field: String,
+    pub title: String,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub author: Vec<Name>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issued: Option<Date>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub publisher: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "publisher-place")]
+    pub publisher_place: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub edition: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "collection-title")]
+    pub collection_title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "number-of-pages")]
+    pub number_of_pages: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "ISBN")]
+    pub isbn: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "URL")]
+    pub url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+}
+
+// Persons have family and given names, organizations a literal
+#[derive(Default, Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct Name {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub family: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub given: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suffix: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub literal: Option<String>,
+}
+
+// {"date-parts": [[1998, 3]]}, or {"literal": "..."} for publish dates that do not parse
+#[derive(Default, Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct Date {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "date-parts")]
+    pub date_parts: Option<Vec<Vec<i32>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub circa: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub literal: Option<String>,
+}
+
+impl Date {
+    fn from_partial(date: PartialDate) -> Self {
+        let mut parts = vec![date.year];
+        if let Some(month) = date.month {
+            parts.push(month as i32);
+            if let Some(day) = date.day {
+                parts.push(day as i32);
+            }
+        }
+
+        Self {
+            date_parts: Some(vec![parts]),
+            circa: if date.circa { Some(true) } else { None },
+            literal: None,
+        }
+    }
+
+    pub fn year(&self) -> Option<i32> {
+        self.date_parts.as_ref()?.first()?.first().copied()
+    }
+}
+
+impl Name {
+    fn from_author(author: &Author) -> Self {
+        if author.is_organization() {
+            return Self {
+                literal: Some(author.name.trim().to_string()),
+                ..Self::default()
+            };
+        }
+
+        let name = author.personal_name();
+        Self {
+            family: Some(name.family),
+            given: name.given,
+            suffix: name.suffix,
+            literal: None,
+        }
+    }
+
+    fn family_or_literal(&self) -> &str {
+        self.family.as_deref().or(self.literal.as_deref()).unwrap_or_default()
+    }
+
+    // "Austen, Jane" or "King, Martin Luther, Jr."
+    fn inverted(&self) -> String {
+        let mut name = self.family_or_literal().to_string();
+        for part in [&self.given, &self.suffix].iter().copied().flatten() {
+            name.push_str(", ");
+            name.push_str(part);
+        }
+
+        name
+    }
+
+    // "Jane Austen" or "Martin Luther King Jr."
+    fn natural(&self) -> String {
+        let parts: Vec<&str> = [self.given.as_deref(), Some(self.family_or_literal()), self.suffix.as_deref()]
+            .iter()
+            .flatten()
+            .copied()
+            .collect();
+
+        parts.join(" ")
+    }
+
+    // APA's "Austen, J."
+    fn initialed(&self) -> String {
+        if self.literal.is_some() {
+            return self.family_or_literal().to_string();
+        }
+
+        let initials = self.given.as_deref().map(initials);
+
+        let mut name = self.family_or_literal().to_string();
+        for part in [&initials, &self.suffix].iter().copied().flatten() {
+            name.push_str(", ");
+            name.push_str(part);
+        }
+
+        name
+    }
+}
+
+impl Entity {
+    pub fn to_csl_json(&self) -> Item {
+        let edition = &self.edition;
+
+        let title = self.full_title();
+
+        let issued = match edition.parsed_publish_date().or_else(|| self.work.parsed_first_publish_date()) {
+            Some(date) => Some(Date::from_partial(date)),
+            None => edition.publish_date.as_ref().map(|literal| Date {
+                literal: Some(literal.clone()),
+                ..Date::default()
+            }),
+        };
+
+        let join = |values: &Option<Vec<String>>| values.as_ref().filter(|values| !values.is_empty()).map(|values| values.join("; "));
+
+        Item {
+            id: self.olid.clone(),
+            type_field: String::from("book"),
+            title,
+            author: self.authors.iter().map(Name::from_author).collect(),
+            issued,
+            publisher: join(&edition.publishers),
+            publisher_place: join(&edition.publish_places),
+            edition: edition.edition_statement().map(String::from),
+            collection_title: join(&edition.series),
+            number_of_pages: edition.page_count().map(|pages| pages.to_string()),
+            isbn: edition.isbn13.iter().chain(edition.isbn10.iter()).flatten().next().cloned(),
+            url: Some(format!("https://openlibrary.org/books/{}", self.olid)),
+            language: edition
+                .languages
+                .iter()
+                .flatten()
+                .next()
+                .map(|language| language.iso639_1().unwrap_or_else(|| language.code()).to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Style {
+    // APA 7th edition
+    Apa,
+    // MLA 9th edition
+    Mla,
+    // Chicago Manual of Style 17th edition, author-date
+    Chicago,
+}
+
+// Citations are plain text, titles are not italicized
+impl Style {
+    // The in-text citation, "(Austen, 1813)", "(Austen)" or "(Austen 1813)"
+    pub fn citation(&self, item: &Item) -> String {
+        let year = item.issued.as_ref().and_then(Date::year);
+        let names: Vec<&str> = item.author.iter().map(Name::family_or_literal).collect();
+
+        let authors = match (self, names.as_slice()) {
+            (_, []) => short_title(&item.title),
+            (_, [one]) => one.to_string(),
+            (Style::Apa, [first, second]) => format!("{} & {}", first, second),
+            (_, [first, second]) => format!("{} and {}", first, second),
+            (_, [first, ..]) => format!("{} et al.", first),
+        };
+
+        match self {
+            Style::Apa => format!("({}, {})", authors, year_or_nd(year)),
+            Style::Mla => format!("({})", authors),
+            Style::Chicago => format!("({} {})", authors, year_or_nd(year)),
+        }
+    }
+
+    // One bibliography entry
+    pub fn reference(&self, item: &Item) -> String {
+        let year = item.issued.as_ref().and_then(Date::year);
+        let edition = item.edition.as_deref().and_then(edition_label);
+        let mut parts: Vec<String> = Vec::new();
+
+        match self {
+            // Austen, J., & King, M. L., Jr. (1813). Pride and prejudice (2nd ed.). T. Egerton.
+            Style::Apa => {
+                if !item.author.is_empty() {
+                    parts.push(terminate(&apa_authors(&item.author)));
+                }
+                parts.push(format!("({}).", year_or_nd(year)));
+                match &edition {
+                    Some(edition) => parts.push(format!("{} ({}).", item.title, edition)),
+                    None => parts.push(terminate(&item.title)),
+                }
+                if let Some(publisher) = &item.publisher {
+                    parts.push(terminate(publisher));
+                }
+            }
+            // Austen, Jane, and Martin Luther King Jr. Pride and Prejudice. 2nd ed., T. Egerton, 1813.
+            Style::Mla => {
+                if !item.author.is_empty() {
+                    parts.push(terminate(&mla_authors(&item.author)));
+                }
+                parts.push(terminate(&item.title));
+                let publication: Vec<String> = [edition, item.publisher.clone(), year.map(|year| year.to_string())]
+                    .iter()
+                    .flatten()
+                    .cloned()
+                    .collect();
+                if !publication.is_empty() {
+                    parts.push(terminate(&publication.join(", ")));
+                }
+            }
+            // Austen, Jane. 1813. Pride and Prejudice. 2nd ed. London: T. Egerton.
+            Style::Chicago => {
+                if !item.author.is_empty() {
+                    parts.push(terminate(&chicago_authors(&item.author)));
+                }
+                parts.push(terminate(&year_or_nd(year)));
+                parts.push(terminate(&item.title));
+                if let Some(edition) = edition {
+                    parts.push(terminate(&edition));
+                }
+                match (&item.publisher_place, &item.publisher) {
+                    (Some(place), Some(publisher)) => parts.push(terminate(&format!("{}: {}", place, publisher))),
+                    (None, Some(publisher)) => parts.push(terminate(publisher)),
+                    (Some(place), None) => parts.push(terminate(place)),
+                    (None, None) => {}
+                }
+            }
+        }
+
+        parts.join(" ")
+    }
+
+    // References sorted by author, then year and title, one per line
+    pub fn bibliography(&self, items: &[Item]) -> String {
+        let mut items: Vec<&Item> = items.iter().collect();
+        items.sort_by_key(|item| {
+            let mut authors: Vec<String> = item.author.iter().map(|name| name.family_or_literal().to_lowercase()).collect();
+            if authors.is_empty() {
+                authors.push(item.title.to_lowercase());
+            }
+            let year = item.issued.as_ref().and_then(Date::year);
+            (authors, year, item.title.to_lowercase())
+        });
+
+        let references: Vec<String> = items.iter().map(|item| self.reference(item)).collect();
+
+        references.join("\n")
+    }
+}
+
+// Up to 20 authors are listed, beyond that the first 19, an ellipsis and the last
+fn apa_authors(names: &[Name]) -> String {
+    let names: Vec<String> = names.iter().map(Name::initialed).collect();
+
+    match names.as_slice() {
+        [one] => one.clone(),
+        [first, second] => format!("{}, & {}", first, second),
+        [rest @ .., last] if names.len() <= 20 => format!("{}, & {}", rest.join(", "), last),
+        [.., last] => format!("{}, . . . {}", names[..19].join(", "), last),
+        [] => String::new(),
+    }
+}
+
+// Two authors are both named, three or more become "et al."
+fn mla_authors(names: &[Name]) -> String {
+    match names {
+        [one] => one.inverted(),
+        [first, second] => format!("{}, and {}", first.inverted(), second.natural()),
+        [first, ..] => format!("{}, et al.", first.inverted()),
+        [] => String::new(),
+    }
+}
+
+// All authors up to ten, beyond that the first seven and "et al."
+fn chicago_authors(names: &[Name]) -> String {
+    let (listed, et_al) = if names.len() > 10 { (&names[..7], true) } else { (names, false) };
+    let mut rendered: Vec<String> = listed.iter().enumerate().map(|(i, name)| if i == 0 { name.inverted() } else { name.natural() }).collect();
+
+    if et_al {
+        return format!("{}, et al.", rendered.join(", "));
+    }
+
+    match rendered.len() {
+        0 | 1 => rendered.join(""),
+        2 => format!("{}, and {}", rendered[0], rendered[1]),
+        _ => {
+            let last = rendered.pop().unwrap_or_default();
+            format!("{}, and {}", rendered.join(", "), last)
+        }
+    }
+}
+
+// "2nd edition", "2nd ed" and "2nd ed." all become "2nd ed.", a bare "2nd" gets the "ed." added.
+// Statements that already name the edition elsewhere, like "2nd ed., rev.", are kept as they are.
+fn edition_label(edition: &str) -> Option<String> {
+    let edition = edition.trim();
+    let trimmed = edition.trim_end_matches('.').trim_end();
+    let lower = trimmed.to_lowercase();
+
+    let stem = if lower.ends_with(" edition") {
+        &trimmed[..trimmed.len() - " edition".len()]
+    } else if lower.ends_with(" ed") {
+        &trimmed[..trimmed.len() - " ed".len()]
+    } else if lower.split(|c: char| !c.is_alphanumeric()).any(|word| word == "ed" || word == "edition") {
+        return Some(edition.to_string());
+    } else {
+        trimmed
+    };
+
+    if stem.trim().is_empty() {
+        None
+    } else {
+        Some(format!("{} ed.", stem.trim()))
+    }
+}
+
+fn year_or_nd(year: Option<i32>) -> String {
+    year.map(|year| year.to_string()).unwrap_or_else(|| String::from("n.d."))
+}
+
+// Ends a sentence without doubling the period after initials or "ed."
+fn terminate(text: &str) -> String {
+    let text = text.trim();
+    if text.ends_with(['.', '?', '!']) {
+        text.to_string()
+    } else {
+        format!("{}.", text)
+    }
+}
+
+// Works without authors are cited by title, up to the first four words
+fn short_title(title: &str) -> String {
+    let short: Vec<&str> = title.split(':').next().unwrap_or_default().split_whitespace().take(4).collect();
+
+    format!("\"{}\"", short.join(" "))
+}
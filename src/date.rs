@@ -0,0 +1,260 @@
+use std::fmt;
+
+use chrono::{NaiveDate, NaiveDateTime, Timelike};
+
+const DATETIME_TYPE: &str = "/type/datetime";
+
+// Open Library writes microseconds, older records have none
+const FORMATS: [&str; 4] = ["%Y-%m-%dT%H:%M:%S%.f", "%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M:%S%.f", "%Y-%m-%d %H:%M:%S"];
+
+// The {"type": "/type/datetime", "value": "2008-04-01T03:28:50.625462"} values
+// used for created and last_modified on every record. The value is parsed when the record is
+// read; a timestamp in an unexpected format leaves it None instead of failing the whole record.
+#[derive(Default, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, serde_derive::Serialize, serde_derive::Deserialize)]
+#[serde(from = "RawDatetime", into = "RawDatetime")]
+pub struct Datetime {
+    pub type_field: String,
+    pub value: Option<NaiveDateTime>,
+    // The value as read, written back unchanged unless value was changed
+    raw: String,
+}
+
+impl Datetime {
+    pub fn new(value: NaiveDateTime) -> Self {
+        Self {
+            type_field: String::from(DATETIME_TYPE),
+            value: Some(value),
+            raw: format_datetime(&value),
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        parse_datetime(value).map(Self::new)
+    }
+
+    fn written(&self) -> String {
+        match self.value {
+            Some(value) if parse_datetime(&self.raw) != Some(value) => format_datetime(&value),
+            _ => self.raw.clone(),
+        }
+    }
+}
+
+impl fmt::Display for Datetime {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.written())
+    }
+}
+
+#[derive(serde_derive::Serialize, serde_derive::Deserialize)]
+struct RawDatetime {
+    #[serde(rename = "type")]
+    type_field: String,
+    value: String,
+}
+
+impl From<RawDatetime> for Datetime {
+    fn from(raw: RawDatetime) -> Self {
+        Self {
+            type_field: raw.type_field,
+            value: parse_datetime(&raw.value),
+            raw: raw.value,
+        }
+    }
+}
+
+impl From<Datetime> for RawDatetime {
+    fn from(datetime: Datetime) -> Self {
+        Self {
+            value: datetime.written(),
+            type_field: datetime.type_field,
+        }
+    }
+}
+
+pub type Created = Datetime;
+pub type LastModified = Datetime;
+
+pub(crate) fn parse_datetime(value: &str) -> Option<NaiveDateTime> {
+    let value = value.trim();
+
+    FORMATS.iter().find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+}
+
+pub(crate) fn format_datetime(value: &NaiveDateTime) -> String {
+    if value.nanosecond() == 0 {
+        value.format("%Y-%m-%dT%H:%M:%S").to_string()
+    } else {
+        value.format("%Y-%m-%dT%H:%M:%S%.6f").to_string()
+    }
+}
+
+// A publication date as catalogued: "c1998", "March 2001", "1st ed. 1985", "[199-?]".
+// Fields are ordered so that sorting PartialDates sorts chronologically.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PartialDate {
+    pub year: i32,
+    pub month: Option<u32>,
+    pub day: Option<u32>,
+    // "c1998", "ca. 1998", "circa 1998"
+    pub circa: bool,
+    // "1998?", "199-" or "19--"
+    pub uncertain: bool,
+}
+
+const MONTHS: [&str; 12] = ["jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec"];
+
+impl PartialDate {
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim().to_lowercase();
+        let mut date = PartialDate {
+            uncertain: text.contains('?'),
+            ..PartialDate::default()
+        };
+
+        let tokens: Vec<&str> = text
+            .split(|c: char| !c.is_ascii_alphanumeric() && c != '-')
+            .filter(|token| !token.is_empty())
+            .collect();
+
+        for (index, token) in tokens.iter().enumerate() {
+            if matches!(*token, "c" | "ca" | "circa" | "approximately") {
+                date.circa = true;
+                continue;
+            }
+
+            // 2001-03-15 and 2001-03
+            if let Some((year, month, day)) = parse_iso(token) {
+                date.year = year;
+                date.month = Some(month);
+                date.day = day;
+                break;
+            }
+
+            let (circa, digits) = match token.strip_prefix('c') {
+                Some(digits) if digits.starts_with(|c: char| c.is_ascii_digit()) => (true, digits),
+                _ => (false, *token),
+            };
+
+            if let Some((year, uncertain)) = parse_year(digits) {
+                date.year = year;
+                date.circa |= circa;
+                date.uncertain |= uncertain;
+
+                // "March 15, 2001" or "15 March 2001", only next to the month so "2nd ed." is no day
+                let month = tokens.iter().enumerate().find_map(|(i, token)| parse_month(token).map(|month| (i, month)));
+                if let Some((month_index, month)) = month {
+                    date.month = Some(month);
+                    date.day = [month_index.checked_sub(1), Some(month_index + 1)]
+                        .iter()
+                        .flatten()
+                        .filter(|&&i| i != index)
+                        .find_map(|&i| tokens.get(i).and_then(|token| parse_day(token)));
+                }
+                break;
+            }
+        }
+
+        if date.year == 0 {
+            return None;
+        }
+
+        // Drop a day that does not exist in that month rather than the whole date
+        if let (Some(month), Some(day)) = (date.month, date.day) {
+            if NaiveDate::from_ymd_opt(date.year, month, day).is_none() {
+                date.day = None;
+            }
+        }
+
+        Some(date)
+    }
+
+    // "1998", "1998-03" or "1998-03-15", as used by W3CDTF and schema.org
+    pub fn to_iso8601(&self) -> String {
+        match (self.month, self.day) {
+            (Some(month), Some(day)) => format!("{:04}-{:02}-{:02}", self.year, month, day),
+            (Some(month), None) => format!("{:04}-{:02}", self.year, month),
+            _ => format!("{:04}", self.year),
+        }
+    }
+
+    // The earliest day the date could refer to
+    pub fn first_day(&self) -> Option<NaiveDate> {
+        NaiveDate::from_ymd_opt(self.year, self.month.unwrap_or(1), self.day.unwrap_or(1))
+    }
+}
+
+impl fmt::Display for PartialDate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.circa {
+            f.write_str("ca. ")?;
+        }
+        write!(f, "{:04}", self.year)?;
+        if let Some(month) = self.month {
+            write!(f, "-{:02}", month)?;
+            if let Some(day) = self.day {
+                write!(f, "-{:02}", day)?;
+            }
+        }
+        if self.uncertain {
+            f.write_str("?")?;
+        }
+
+        Ok(())
+    }
+}
+
+fn parse_iso(token: &str) -> Option<(i32, u32, Option<u32>)> {
+    let parts: Vec<&str> = token.split('-').collect();
+    if parts.len() < 2 || parts.len() > 3 || parts[0].len() != 4 || parts[1].len() != 2 {
+        return None;
+    }
+
+    let year = parts[0].parse().ok()?;
+    let month = parts[1].parse().ok().filter(|month| (1..=12).contains(month))?;
+    let day = match parts.get(2) {
+        Some(day) => Some(day.parse().ok().filter(|day| (1..=31).contains(day))?),
+        None => None,
+    };
+
+    Some((year, month, day))
+}
+
+// "1998", "1985-86", "199-" (some year in the 1990s) and "19--" (some year in the 1900s)
+fn parse_year(token: &str) -> Option<(i32, bool)> {
+    let head = token.get(..4)?;
+    if !token[4..].is_empty() && !token[4..].starts_with('-') {
+        return None;
+    }
+
+    let digits = head.trim_end_matches('-');
+    if digits.len() < 2 || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    let uncertain = digits.len() < 4;
+    let year: i32 = format!("{:0<4}", digits).parse().ok()?;
+
+    Some((year, uncertain))
+}
+
+fn parse_month(token: &str) -> Option<u32> {
+    if token.len() < 3 {
+        return None;
+    }
+
+    MONTHS
+        .iter()
+        .position(|month| token.starts_with(month))
+        .map(|index| index as u32 + 1)
+}
+
+// "15" or "15th"
+fn parse_day(token: &str) -> Option<u32> {
+    let digits = token.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+    if digits.is_empty() || digits.len() > 2 || !matches!(&token[digits.len()..], "" | "st" | "nd" | "rd" | "th") {
+        return None;
+    }
+
+    digits.parse().ok().filter(|day| (1..=31).contains(day))
+}
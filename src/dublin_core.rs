@@ -0,0 +1,98 @@
+use crate::entity::Entity;
+use crate::xml;
+
+const OAI_DC_NAMESPACE: &str = "http://www.openarchives.org/OAI/2.0/oai_dc/";
+const DC_NAMESPACE: &str = "http://purl.org/dc/elements/1.1/";
+const OAI_DC_SCHEMA_LOCATION: &str = "http://www.openarchives.org/OAI/2.0/oai_dc/ http://www.openarchives.org/OAI/2.0/oai_dc.xsd";
+
+impl Entity {
+    // Simple Dublin Core in the oai_dc container that OAI-PMH harvesters expect
+    pub fn to_dublin_core_xml(&self) -> String {
+        let edition = &self.edition;
+        let mut writer = xml::Writer::new();
+
+        writer.start(
+            "oai_dc:dc",
+            &[
+                ("xmlns:oai_dc", OAI_DC_NAMESPACE),
+                ("xmlns:dc", DC_NAMESPACE),
+                ("xmlns:xsi", "http://www.w3.org/2001/XMLSchema-instance"),
+                ("xsi:schemaLocation", OAI_DC_SCHEMA_LOCATION),
+            ],
+        );
+
+        writer.element("dc:title", &[], &self.full_title());
+        for author in self.authors.iter() {
+            let name = if author.is_organization() {
+                author.name.trim().to_string()
+            } else {
+                let name = author.personal_name();
+                match name.given {
+                    Some(given) => format!("{}, {}", name.family, given),
+                    None => name.family,
+                }
+            };
+            writer.element("dc:creator", &[], &name);
+        }
+        for contributor in edition.contributions.iter().flatten() {
+            writer.element("dc:contributor", &[], contributor);
+        }
+        for subject in self.subjects() {
+            writer.element("dc:subject", &[], subject);
+        }
+        if let Some(description) = self.description() {
+            writer.element("dc:description", &[], description);
+        }
+        for publisher in edition.publishers.iter().flatten() {
+            writer.element("dc:publisher", &[], publisher);
+        }
+
+        // W3CDTF when the publish date parses, as catalogued otherwise
+        let date = edition
+            .parsed_publish_date()
+            .map(|date| date.to_iso8601())
+            .or_else(|| edition.publish_date.clone());
+        if let Some(date) = date {
+            writer.element("dc:date", &[], &date);
+        }
+
+        writer.element("dc:type", &[], "Text");
+        if let Some(format) = &edition.physical_format {
+            writer.element("dc:format", &[], format);
+        }
+        if let Some(extent) = edition.page_count().map(|pages| format!("{} pages", pages)) {
+            writer.element("dc:format", &[], &extent);
+        }
+
+        for identifier in identifier_uris(self) {
+            writer.element("dc:identifier", &[], &identifier);
+        }
+        for language in edition.languages.iter().flatten() {
+            writer.element("dc:language", &[], language.code());
+        }
+        for series in edition.series.iter().flatten() {
+            writer.element("dc:relation", &[], series);
+        }
+
+        writer.finish()
+    }
+}
+
+// ISBNs as URNs, LCCN and OCLC numbers as their resolver URLs, and the Open Library page
+pub(crate) fn identifier_uris(entity: &Entity) -> Vec<String> {
+    let edition = &entity.edition;
+    let mut uris = Vec::new();
+
+    for isbn in edition.isbn13.iter().chain(edition.isbn10.iter()).flatten() {
+        uris.push(format!("urn:isbn:{}", isbn.replace(['-', ' '], "")));
+    }
+    for lccn in edition.lccn.iter().flatten() {
+        uris.push(format!("https://lccn.loc.gov/{}", lccn.replace(' ', "")));
+    }
+    for oclc in edition.oclc_numbers.iter().flatten() {
+        uris.push(format!("https://www.worldcat.org/oclc/{}", oclc.trim()));
+    }
+    uris.push(format!("https://openlibrary.org/books/{}", entity.olid));
+
+    uris
+}
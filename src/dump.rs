@@ -0,0 +1,203 @@
+use std::error;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+use std::str;
+
+use flate2::read::GzDecoder;
+use serde_json::Value;
+
+use crate::edition::Edition;
+use crate::work::Work;
+use crate::author::Author;
+
+// https://openlibrary.org/developers/dumps
+// Every row of ol_dump_{editions,works,authors}_*.txt.gz is
+// type \t key \t revision \t last_modified \t JSON
+const COLUMNS: usize = 5;
+
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone, PartialEq)]
+pub enum Record {
+    Edition(Edition),
+    Work(Work),
+    Author(Author),
+    // Redirects, deletions and anything else found in the "all" dump
+    Other {
+        type_key: String,
+        key: String,
+        json: Value,
+    },
+}
+
+impl Record {
+    pub fn key(&self) -> &str {
+        match self {
+            Record::Edition(edition) => &edition.key,
+            Record::Work(work) => &work.key,
+            Record::Author(author) => &author.key,
+            Record::Other { key, .. } => key,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ErrorKind {
+    Io(io::Error),
+    Utf8(str::Utf8Error),
+    MissingColumns(usize),
+    Json(serde_json::Error),
+}
+
+#[derive(Debug)]
+pub struct Error {
+    line: usize,
+    kind: ErrorKind,
+}
+
+impl Error {
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    pub fn kind(&self) -> &ErrorKind {
+        &self.kind
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.kind {
+            ErrorKind::Io(err) => write!(f, "line {}: {}", self.line, err),
+            ErrorKind::Utf8(err) => write!(f, "line {}: row is not valid UTF-8: {}", self.line, err),
+            ErrorKind::MissingColumns(found) => write!(f, "line {}: expected {} columns, found {}", self.line, COLUMNS, found),
+            ErrorKind::Json(err) => write!(f, "line {}: invalid record JSON: {}", self.line, err),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match &self.kind {
+            ErrorKind::Io(err) => Some(err),
+            ErrorKind::Utf8(err) => Some(err),
+            ErrorKind::MissingColumns(_) => None,
+            ErrorKind::Json(err) => Some(err),
+        }
+    }
+}
+
+pub struct Reader<R> {
+    inner: R,
+    line: usize,
+    buf: Vec<u8>,
+    done: bool,
+}
+
+impl Reader<BufReader<GzDecoder<File>>> {
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let file = File::open(path)?;
+
+        Ok(Self::new(BufReader::new(GzDecoder::new(file))))
+    }
+}
+
+impl<R: BufRead> Reader<R> {
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            line: 0,
+            buf: Vec::new(),
+            done: false,
+        }
+    }
+
+    pub fn editions(self) -> impl Iterator<Item = Result<Edition, Error>> {
+        self.filter_map(|record| match record {
+            Ok(Record::Edition(edition)) => Some(Ok(edition)),
+            Ok(_) => None,
+            Err(err) => Some(Err(err)),
+        })
+    }
+
+    pub fn works(self) -> impl Iterator<Item = Result<Work, Error>> {
+        self.filter_map(|record| match record {
+            Ok(Record::Work(work)) => Some(Ok(work)),
+            Ok(_) => None,
+            Err(err) => Some(Err(err)),
+        })
+    }
+
+    pub fn authors(self) -> impl Iterator<Item = Result<Author, Error>> {
+        self.filter_map(|record| match record {
+            Ok(Record::Author(author)) => Some(Ok(author)),
+            Ok(_) => None,
+            Err(err) => Some(Err(err)),
+        })
+    }
+
+    fn error(&self, kind: ErrorKind) -> Error {
+        Error {
+            line: self.line,
+            kind,
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for Reader<R> {
+    type Item = Result<Record, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            self.buf.clear();
+            self.line += 1;
+
+            // Read bytes rather than a String so one badly encoded row is reported on its own
+            // instead of ending the whole dump
+            match self.inner.read_until(b'\n', &mut self.buf) {
+                Ok(0) => self.done = true,
+                Ok(_) => {
+                    let row = match str::from_utf8(&self.buf) {
+                        Ok(row) => row.trim_end_matches(&['\n', '\r'][..]),
+                        Err(err) => return Some(Err(self.error(ErrorKind::Utf8(err)))),
+                    };
+                    if row.is_empty() {
+                        continue;
+                    }
+
+                    return Some(parse_row(row).map_err(|kind| self.error(kind)));
+                }
+                Err(err) => {
+                    // A broken gzip stream or failing read will not recover, so stop after reporting it
+                    self.done = true;
+                    return Some(Err(self.error(ErrorKind::Io(err))));
+                }
+            }
+        }
+
+        None
+    }
+}
+
+fn parse_row(row: &str) -> Result<Record, ErrorKind> {
+    let columns: Vec<&str> = row.splitn(COLUMNS, '\t').collect();
+    if columns.len() < COLUMNS {
+        return Err(ErrorKind::MissingColumns(columns.len()));
+    }
+
+    let json = columns[COLUMNS - 1];
+
+    let record = match columns[0] {
+        "/type/edition" => Record::Edition(serde_json::from_str(json).map_err(ErrorKind::Json)?),
+        "/type/work" => Record::Work(serde_json::from_str(json).map_err(ErrorKind::Json)?),
+        "/type/author" => Record::Author(serde_json::from_str(json).map_err(ErrorKind::Json)?),
+        type_key => Record::Other {
+            type_key: String::from(type_key),
+            key: String::from(columns[1]),
+            json: serde_json::from_str(json).map_err(ErrorKind::Json)?,
+        },
+    };
+
+    Ok(record)
+}
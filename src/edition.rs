@@ -1,19 +1,19 @@
-use std::fmt;
-use std::collections::BTreeMap as Map;
-use std::marker::PhantomData;
-use std::str::FromStr;
+use std::collections::BTreeMap;
 
-use serde::{Deserialize, Deserializer};
-use serde::de::{self, Visitor, MapAccess};
-use void::Void;
+use serde_json::{Map, Value};
+
+use crate::canonical::{is_default, read_record, write_record, Elided};
+use crate::date::{Created, LastModified, PartialDate};
 
 #[derive(Default, Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[serde(remote = "Self")]
 pub struct Edition {
 
     pub key: String,
     pub title: String,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "full_title")]
     pub full_title: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub subtitle: Option<String>,
@@ -21,6 +21,9 @@ pub struct Edition {
     pub type_field: Type,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub authors: Option<Vec<Authors>>,
+    // Orphaned editions in the dumps have no works
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
     pub works: Vec<Works>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub identifiers: Option<Identifiers>,
@@ -40,14 +43,14 @@ pub struct Edition {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub covers: Option<Vec<isize>>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub links: Option<String>,
+    pub links: Option<Vec<Link>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub languages: Option<Vec<Language>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "by_statement")]
     pub by_statement: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub weight: Option<Weight>,
+    pub weight: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "edition_name")]
     pub edition_name: Option<String>, //OL16341636M uses a string here instead of a EditionName struct
@@ -57,8 +60,8 @@ pub struct Edition {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub pagination: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "physical_dimensinos")]
-    pub physical_dimensions: Option<PhysicalDimensions>,
+    #[serde(rename = "physical_dimensions")]
+    pub physical_dimensions: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "physical_format")]
     pub physical_format: Option<String>,
@@ -69,6 +72,7 @@ pub struct Edition {
     #[serde(rename = "publish_date")]
     pub publish_date: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "publish_places")]
     pub publish_places: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub publishers: Option<Vec<String>>,
@@ -99,10 +103,10 @@ pub struct Edition {
     #[serde(rename = "table_of_contents")]
     pub table_of_contents: Option<Vec<TableOfContents>>, //https://openlibrary.org/books/OL26443497M/Anarchism_in_Latin_America
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub description: Option<String>,
+    pub description: Option<Note>,
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "first_sentence")]
-    pub first_sentence: Option<String>,
+    pub first_sentence: Option<Note>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub notes: Option<Note>,
     pub revision: usize,
@@ -118,11 +122,32 @@ pub struct Edition {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "ia_box_id")]
     pub ia_box_id: Option<Vec<String>>,
+    // Fields this crate does not model, kept so that saving a record does not drop them
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+    // Explicit nulls and empty values, kept so that saving a record does not drop them either
+    #[serde(skip)]
+    pub elided: Elided,
+}
+
+impl<'de> serde::Deserialize<'de> for Edition {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let (mut record, elided) = read_record(deserializer, Edition::deserialize)?;
+        record.elided = elided;
+
+        Ok(record)
+    }
+}
+
+impl serde::Serialize for Edition {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        write_record(serializer, &self.elided, |value| Edition::serialize(self, value))
+    }
 }
 
 impl Edition {
     pub fn get_author (&self) -> Vec<Authors> {
-        self.authors.as_ref().unwrap().clone()
+        self.authors.clone().unwrap_or_default()
     }
 
     pub fn get_works_ids (&self) -> Vec<String> {
@@ -132,7 +157,20 @@ impl Edition {
             work_ids.push(works.key.clone());
         }
 
-        return work_ids;
+        work_ids
+    }
+
+    pub fn parsed_publish_date(&self) -> Option<PartialDate> {
+        self.publish_date.as_deref().and_then(PartialDate::parse)
+    }
+
+    pub fn publish_year(&self) -> Option<i32> {
+        self.parsed_publish_date().map(|date| date.year)
+    }
+
+    // The trimmed edition_name, None when it is missing or blank as in many imported records
+    pub(crate) fn edition_statement(&self) -> Option<&str> {
+        self.edition_name.as_deref().map(str::trim).filter(|name| !name.is_empty())
     }
 }
 
@@ -140,6 +178,7 @@ impl Edition {
 #[serde(rename_all = "camelCase")]
 pub struct Notes {
     #[serde(default)]
+    #[serde(skip_serializing_if = "is_default")]
     #[serde(rename = "type")]
     pub type_field: String,
     pub value: String,
@@ -153,6 +192,15 @@ pub enum Note {
     NoteString(String),
 }
 
+impl Note {
+    pub fn text(&self) -> &str {
+        match self {
+            Note::NoteType(note) => &note.value,
+            Note::NoteString(note) => note,
+        }
+    }
+}
+
 #[derive(Default, Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Title {
@@ -179,40 +227,170 @@ pub struct Authors {
     pub key: String,
 }
 
+#[derive(Default, Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct Link {
+    pub url: String,
+    pub title: String,
+    #[serde(rename = "type")]
+    pub type_field: Type,
+}
+
 #[derive(Default, Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Works {
     pub key: String,
 }
 
+// The identifier kinds Open Library offers in its edit form, anything else ends up in other
 #[derive(Default, Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
-#[serde(rename_all = "camelCase")]
 pub struct Identifiers {
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub librarything: Option<Vec<String>>,
+    pub alibris_id: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub amazon: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bcid: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub better_world_books: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bibsys: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub british_library: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dnb: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fennica: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub goodreads: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub google: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hathi_trust: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub isfdb: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub librarything: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub librivox: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lulu: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nla: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub openstax: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub overdrive: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub paperback_swap: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_gutenberg: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_runeberg: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scribd: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shelfari: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub smashwords_book_download: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub standard_ebooks: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub storygraph: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wikidata: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wikisource: Option<Vec<String>>,
+    #[serde(flatten)]
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub other: BTreeMap<String, Vec<String>>,
 }
 
-#[derive(Default, Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct Language {
+impl Identifiers {
+    // kind is the JSON key, e.g. "goodreads" or "amazon.co.uk_asin"
+    pub fn get(&self, kind: &str) -> Option<&[String]> {
+        match self.known().iter().find(|(name, _)| *name == kind) {
+            Some((_, values)) => values.as_deref(),
+            None => self.other.get(kind).map(Vec::as_slice),
+        }
+    }
+
+    // Every identifier present on the edition, known kinds first
+    pub fn iter(&self) -> Vec<(&str, &[String])> {
+        let mut identifiers: Vec<(&str, &[String])> = self
+            .known()
+            .iter()
+            .filter_map(|(name, values)| values.as_deref().map(|values| (*name, values)))
+            .collect();
+        identifiers.extend(self.other.iter().map(|(name, values)| (name.as_str(), values.as_slice())));
+
+        identifiers
+    }
+
+    fn known(&self) -> [(&'static str, &Option<Vec<String>>); 28] {
+        [
+            ("alibris_id", &self.alibris_id),
+            ("amazon", &self.amazon),
+            ("bcid", &self.bcid),
+            ("better_world_books", &self.better_world_books),
+            ("bibsys", &self.bibsys),
+            ("british_library", &self.british_library),
+            ("dnb", &self.dnb),
+            ("fennica", &self.fennica),
+            ("goodreads", &self.goodreads),
+            ("google", &self.google),
+            ("hathi_trust", &self.hathi_trust),
+            ("isfdb", &self.isfdb),
+            ("librarything", &self.librarything),
+            ("librivox", &self.librivox),
+            ("lulu", &self.lulu),
+            ("nla", &self.nla),
+            ("openstax", &self.openstax),
+            ("overdrive", &self.overdrive),
+            ("paperback_swap", &self.paperback_swap),
+            ("project_gutenberg", &self.project_gutenberg),
+            ("project_runeberg", &self.project_runeberg),
+            ("scribd", &self.scribd),
+            ("shelfari", &self.shelfari),
+            ("smashwords_book_download", &self.smashwords_book_download),
+            ("standard_ebooks", &self.standard_ebooks),
+            ("storygraph", &self.storygraph),
+            ("wikidata", &self.wikidata),
+            ("wikisource", &self.wikisource),
+        ]
+    }
+}
+
+// One entry of the Books API response, keyed by the requested bibkey like "OCLC:1234"
+#[derive(Default, Debug, Clone, PartialEq, serde_derive::Deserialize)]
+pub(crate) struct BibkeyRecord {
+    pub key: String,
+}
+
+// /search.json?q=id_goodreads:... with fields=key,editions
+#[derive(Default, Debug, Clone, PartialEq, serde_derive::Deserialize)]
+pub(crate) struct IdentifierSearch {
+    #[serde(default)]
+    pub docs: Vec<IdentifierSearchDoc>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, serde_derive::Deserialize)]
+pub(crate) struct IdentifierSearchDoc {
+    #[serde(default)]
+    pub editions: Option<IdentifierSearch>,
     pub key: String,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct ByStatement {
-    #[serde(rename = "type")]
-    pub type_field: String,
+pub struct Language {
+    pub key: String,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct Weight {
+pub struct ByStatement {
     #[serde(rename = "type")]
     pub type_field: String,
-    pub examples: Vec<String>,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
@@ -230,14 +408,6 @@ pub struct NumberOfPages {
     pub type_field: usize,
 }
 
-#[derive(Default, Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct PhysicalDimensions {
-    #[serde(rename = "type")]
-    pub type_field: String,
-    pub examples: Vec<String>,
-}
-
 #[derive(Default, Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PhysicalFormat {
@@ -265,6 +435,8 @@ pub struct TableOfContents {
     pub title: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub pagenum: Option<String>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
@@ -273,19 +445,3 @@ pub struct Revision {
     #[serde(rename = "type")]
     pub type_field: String,
 }
-
-#[derive(Default, Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct Created {
-    #[serde(rename = "type")]
-    pub type_field: String,
-    pub value: String,
-}
-
-#[derive(Default, Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct LastModified {
-    #[serde(rename = "type")]
-    pub type_field: String,
-    pub value: String,
-}
@@ -4,31 +4,42 @@ use crate::author::Author;
 
 #[derive(Clone)]
 pub struct Entity {
-    olid: String,
-    edition: Edition,
-    work: Work,
-    author: Author,
+    pub(crate) olid: String,
+    pub(crate) edition: Edition,
+    pub(crate) work: Work,
+    pub(crate) authors: Vec<Author>,
 }
 
 impl Entity {
     pub fn new(olid: String, edition: Edition, work: Work, author: Author) -> Self {
-        return Self {
-            olid: olid,
-            edition: edition,
-            work: work,
-            author: author,
-        };
+        Self::with_authors(olid, edition, work, vec![author])
+    }
+
+    // authors in the order the work lists them
+    pub fn with_authors(olid: String, edition: Edition, work: Work, authors: Vec<Author>) -> Self {
+        Self {
+            olid,
+            edition,
+            work,
+            authors,
+        }
     }
+
     pub fn get_olid(&self) -> String {
         self.olid.clone()
     }
 
+    // The first author
     pub fn get_author(&self) -> Author {
-        self.author.clone()
+        self.authors.first().cloned().unwrap_or_default()
     }
 
     pub fn get_author_name(&self) -> String {
-        self.author.name.clone()
+        self.authors.first().map(|author| author.name.clone()).unwrap_or_default()
+    }
+
+    pub fn get_authors(&self) -> Vec<Author> {
+        self.authors.clone()
     }
 
     pub fn get_work(&self) -> Work {
@@ -42,4 +53,46 @@ impl Entity {
     pub fn get_edition(&self) -> Edition {
         self.edition.clone()
     }
+
+    // The edition's title and subtitle, the work's when the edition has none
+    pub(crate) fn title_parts(&self) -> (&str, Option<&str>) {
+        let (title, subtitle) = if self.edition.title.is_empty() {
+            (&self.work.title, &self.work.subtitle)
+        } else {
+            (&self.edition.title, &self.edition.subtitle)
+        };
+
+        (title.as_str(), subtitle.as_deref().filter(|subtitle| !subtitle.is_empty()))
+    }
+
+    // "Title: Subtitle"
+    pub(crate) fn full_title(&self) -> String {
+        match self.title_parts() {
+            (title, Some(subtitle)) => format!("{}: {}", title, subtitle),
+            (title, None) => title.to_string(),
+        }
+    }
+
+    // Edition subjects followed by the work's, without duplicates
+    pub(crate) fn subjects(&self) -> Vec<&str> {
+        let mut subjects: Vec<&str> = Vec::new();
+        for subject in self.edition.subjects.iter().chain(self.work.subjects.iter()).flatten() {
+            if !subjects.contains(&subject.as_str()) {
+                subjects.push(subject);
+            }
+        }
+
+        subjects
+    }
+
+    // The edition's description, the work's when the edition has none
+    pub(crate) fn description(&self) -> Option<&str> {
+        self.edition
+            .description
+            .as_ref()
+            .map(|description| description.text())
+            .or_else(|| self.work.description.as_ref().map(|description| description.text()))
+            .map(str::trim)
+            .filter(|description| !description.is_empty())
+    }
 }
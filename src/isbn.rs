@@ -0,0 +1,51 @@
+// Conversions between the two ISBN forms, so an edition catalogued with only one of them is found by either
+
+// Digits and a trailing X, without hyphens or spaces
+pub(crate) fn normalize(isbn: &str) -> String {
+    isbn.chars().filter(|c| c.is_ascii_alphanumeric()).collect::<String>().to_uppercase()
+}
+
+pub(crate) fn to_isbn13(isbn: &str) -> Option<String> {
+    let isbn = normalize(isbn);
+    if isbn.len() == 13 && isbn.chars().all(|c| c.is_ascii_digit()) {
+        return Some(isbn);
+    }
+    if isbn.len() != 10 || !isbn[..9].chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    let stem = format!("978{}", &isbn[..9]);
+    let sum: u32 = stem
+        .chars()
+        .filter_map(|c| c.to_digit(10))
+        .enumerate()
+        .map(|(index, digit)| if index % 2 == 0 { digit } else { digit * 3 })
+        .sum();
+
+    Some(format!("{}{}", stem, (10 - sum % 10) % 10))
+}
+
+// Only 978 ISBN-13s have an ISBN-10
+pub(crate) fn to_isbn10(isbn: &str) -> Option<String> {
+    let isbn = normalize(isbn);
+    if isbn.len() == 10 {
+        return Some(isbn);
+    }
+    if isbn.len() != 13 || !isbn.starts_with("978") || !isbn.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    let stem = &isbn[3..12];
+    let sum: u32 = stem
+        .chars()
+        .filter_map(|c| c.to_digit(10))
+        .enumerate()
+        .map(|(index, digit)| (10 - index as u32) * digit)
+        .sum();
+    let check = match (11 - sum % 11) % 11 {
+        10 => 'X',
+        check => std::char::from_digit(check, 10)?,
+    };
+
+    Some(format!("{}{}", stem, check))
+}
@@ -0,0 +1,165 @@
+use serde_json::{json, Map, Value};
+
+use crate::author::Author;
+use crate::covers::CoverSize;
+use crate::date::PartialDate;
+use crate::edition::Edition;
+use crate::entity::Entity;
+
+const OPEN_LIBRARY: &str = "https://openlibrary.org";
+
+impl Entity {
+    // A schema.org Book for the work with the edition as its workExample, for embedding in a
+    // <script type="application/ld+json"> tag
+    pub fn to_json_ld(&self) -> Value {
+        let mut book = Map::new();
+        book.insert("@context".into(), json!("https://schema.org"));
+        book.insert("@type".into(), json!("Book"));
+        if !self.work.key.is_empty() {
+            book.insert("@id".into(), json!(format!("{}{}", OPEN_LIBRARY, self.work.key)));
+            book.insert("url".into(), json!(format!("{}{}", OPEN_LIBRARY, self.work.key)));
+        }
+        let title = if self.work.title.is_empty() { self.title_parts().0 } else { self.work.title.as_str() };
+        book.insert("name".into(), json!(title));
+
+        let authors: Vec<Value> = self.authors.iter().filter(|author| !author.name.is_empty()).map(person).collect();
+        if !authors.is_empty() {
+            book.insert("author".into(), Value::Array(authors));
+        }
+        if let Some(description) = self.description() {
+            book.insert("description".into(), json!(description));
+        }
+        let subjects = self.subjects();
+        if !subjects.is_empty() {
+            book.insert("keywords".into(), json!(subjects.join(", ")));
+        }
+        if let Some(year) = self.work.first_publish_year() {
+            book.insert("datePublished".into(), json!(year.to_string()));
+        }
+
+        let edition = example(&self.edition, &self.full_title());
+        if let Some(image) = edition.get("image") {
+            book.insert("image".into(), image.clone());
+        }
+        book.insert("workExample".into(), json!([edition]));
+
+        Value::Object(book)
+    }
+}
+
+fn example(edition: &Edition, title: &str) -> Map<String, Value> {
+    let mut book = Map::new();
+    book.insert("@type".into(), json!("Book"));
+    if !edition.key.is_empty() {
+        book.insert("@id".into(), json!(format!("{}{}", OPEN_LIBRARY, edition.key)));
+        book.insert("url".into(), json!(format!("{}{}", OPEN_LIBRARY, edition.key)));
+    }
+    book.insert("name".into(), json!(title));
+
+    // schema.org takes a single isbn, the ISBN-13 when there is one
+    let isbn = edition.isbn13.iter().chain(edition.isbn10.iter()).flatten().next();
+    if let Some(isbn) = isbn {
+        book.insert("isbn".into(), json!(isbn.replace(['-', ' '], "")));
+    }
+    if let Some(pages) = edition.page_count() {
+        book.insert("numberOfPages".into(), json!(pages));
+    }
+    if let Some(format) = edition.physical_format.as_deref().and_then(book_format) {
+        book.insert("bookFormat".into(), json!(format));
+    }
+    if let Some(edition_name) = edition.edition_statement() {
+        book.insert("bookEdition".into(), json!(edition_name));
+    }
+
+    // BCP 47 wants the two letter code where one exists
+    let languages: Vec<&str> = edition
+        .languages
+        .iter()
+        .flatten()
+        .map(|language| language.iso639_1().unwrap_or_else(|| language.code()))
+        .collect();
+    match languages.as_slice() {
+        [] => {}
+        [language] => {
+            book.insert("inLanguage".into(), json!(language));
+        }
+        languages => {
+            book.insert("inLanguage".into(), json!(languages));
+        }
+    }
+
+    if let Some(publisher) = edition.publishers.iter().flatten().next() {
+        book.insert("publisher".into(), json!({ "@type": "Organization", "name": publisher }));
+    }
+    if let Some(date) = edition.parsed_publish_date() {
+        book.insert("datePublished".into(), json!(date.to_iso8601()));
+    }
+    if let Some(image) = edition.cover_url(CoverSize::L) {
+        book.insert("image".into(), json!(image));
+    }
+
+    book
+}
+
+fn person(author: &Author) -> Value {
+    let mut person = Map::new();
+    let organization = author.is_organization();
+    person.insert("@type".into(), json!(if organization { "Organization" } else { "Person" }));
+    if !author.key.is_empty() {
+        person.insert("@id".into(), json!(format!("{}{}", OPEN_LIBRARY, author.key)));
+        person.insert("url".into(), json!(format!("{}{}", OPEN_LIBRARY, author.key)));
+    }
+    person.insert("name".into(), json!(author.name.trim()));
+
+    if !organization {
+        let birth_date = author.birth_date.as_deref().and_then(PartialDate::parse);
+        if let Some(birth_date) = birth_date {
+            person.insert("birthDate".into(), json!(birth_date.to_iso8601()));
+        }
+        let death_date = author.death_date.as_deref().and_then(PartialDate::parse);
+        if let Some(death_date) = death_date {
+            person.insert("deathDate".into(), json!(death_date.to_iso8601()));
+        }
+    }
+
+    let mut same_as = Vec::new();
+    if let Some(remote_ids) = &author.remote_ids {
+        if !remote_ids.wikidata.is_empty() {
+            same_as.push(format!("https://www.wikidata.org/wiki/{}", remote_ids.wikidata));
+        }
+        if !remote_ids.viaf.is_empty() {
+            same_as.push(format!("https://viaf.org/viaf/{}", remote_ids.viaf));
+        }
+        if !remote_ids.isni.is_empty() {
+            same_as.push(format!("https://isni.org/isni/{}", remote_ids.isni.replace(' ', "")));
+        }
+    }
+    if let Some(wikipedia) = &author.wikipedia {
+        same_as.push(wikipedia.trim().to_string());
+    }
+    if !same_as.is_empty() {
+        person.insert("sameAs".into(), json!(same_as));
+    }
+
+    Value::Object(person)
+}
+
+// Open Library's free text formats onto schema.org's BookFormatType
+fn book_format(physical_format: &str) -> Option<&'static str> {
+    let format = physical_format.to_lowercase();
+    let book_format = if format.contains("audio") || format.contains("cassette") {
+        "https://schema.org/AudiobookFormat"
+    } else if format.contains("ebook") || format.contains("e-book") || format.contains("electronic") {
+        "https://schema.org/EBook"
+    } else if format.contains("graphic") {
+        "https://schema.org/GraphicNovel"
+    } else if format.contains("hardcover") || format.contains("hardback") || format.contains("hard cover") || format.contains("library binding") {
+        "https://schema.org/Hardcover"
+    } else if format.contains("paperback") || format.contains("paper back") || format.contains("mass market") || format.contains("softcover") {
+        "https://schema.org/Paperback"
+    } else {
+        return None;
+    };
+
+    Some(book_format)
+}
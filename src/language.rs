@@ -0,0 +1,626 @@
+use crate::date::{Created, LastModified};
+use crate::edition::{Edition, Language, Type};
+
+// An entry of the MARC code list for languages, https://www.loc.gov/marc/languages/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LanguageCode {
+    pub marc: &'static str,
+    pub iso639_2b: &'static str,
+    pub iso639_1: Option<&'static str>,
+    pub name: &'static str,
+    // Discontinued MARC codes still found in older records, e.g. "scr" for Croatian
+    pub obsolete: bool,
+}
+
+impl LanguageCode {
+    pub fn all() -> &'static [LanguageCode] {
+        LANGUAGES
+    }
+
+    // Accepts "eng" as well as the "/languages/eng" keys used by Open Library
+    pub fn from_marc(code: &str) -> Option<&'static LanguageCode> {
+        let code = code.trim().trim_start_matches("/languages/").to_ascii_lowercase();
+
+        LANGUAGES
+            .binary_search_by(|language| language.marc.cmp(&code))
+            .ok()
+            .map(|index| &LANGUAGES[index])
+    }
+
+    pub fn from_iso639_1(code: &str) -> Option<&'static LanguageCode> {
+        let code = code.trim().to_ascii_lowercase();
+
+        LANGUAGES
+            .iter()
+            .find(|language| !language.obsolete && language.iso639_1 == Some(code.as_str()))
+    }
+
+    // Tries the MARC code first, then ISO 639-1
+    pub fn lookup(code: &str) -> Option<&'static LanguageCode> {
+        Self::from_marc(code).or_else(|| Self::from_iso639_1(code))
+    }
+}
+
+impl Language {
+    pub fn code(&self) -> &str {
+        self.key.trim_start_matches("/languages/")
+    }
+
+    pub fn language_code(&self) -> Option<&'static LanguageCode> {
+        LanguageCode::from_marc(&self.key)
+    }
+
+    pub fn name(&self) -> Option<&'static str> {
+        self.language_code().map(|language| language.name)
+    }
+
+    pub fn iso639_1(&self) -> Option<&'static str> {
+        self.language_code().and_then(|language| language.iso639_1)
+    }
+
+    pub fn iso639_2b(&self) -> Option<&'static str> {
+        self.language_code().map(|language| language.iso639_2b)
+    }
+}
+
+impl Edition {
+    // English names of the edition's languages, unknown codes are left out
+    pub fn language_names(&self) -> Vec<&'static str> {
+        self.languages
+            .iter()
+            .flatten()
+            .filter_map(|language| language.name())
+            .collect()
+    }
+}
+
+// https://openlibrary.org/languages/eng.json
+#[derive(Default, Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct LanguageRecord {
+    pub key: String,
+    pub name: String,
+    pub code: String,
+    #[serde(rename = "type")]
+    pub type_field: Type,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub library_of_congress_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revision: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latest_revision: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created: Option<Created>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_modified: Option<LastModified>,
+}
+
+impl LanguageRecord {
+    pub fn language_code(&self) -> Option<&'static LanguageCode> {
+        LanguageCode::from_marc(&self.code)
+    }
+}
+
+const fn code(marc: &'static str, iso639_1: Option<&'static str>, name: &'static str) -> LanguageCode {
+    LanguageCode { marc, iso639_2b: marc, iso639_1, name, obsolete: false }
+}
+
+const fn obsolete(marc: &'static str, iso639_2b: &'static str, iso639_1: Option<&'static str>, name: &'static str) -> LanguageCode {
+    LanguageCode { marc, iso639_2b, iso639_1, name, obsolete: true }
+}
+
+// Sorted by MARC code for binary search
+static LANGUAGES: &[LanguageCode] = &[
+    code("aar", Some("aa"), "Afar"),
+    code("abk", Some("ab"), "Abkhaz"),
+    code("ace", None, "Achinese"),
+    code("ach", None, "Acoli"),
+    code("ada", None, "Adangme"),
+    code("ady", None, "Adygei"),
+    code("afa", None, "Afroasiatic (Other)"),
+    code("afh", None, "Afrihili (Artificial language)"),
+    code("afr", Some("af"), "Afrikaans"),
+    code("ain", None, "Ainu"),
+    code("aka", Some("ak"), "Akan"),
+    code("akk", None, "Akkadian"),
+    code("alb", Some("sq"), "Albanian"),
+    code("ale", None, "Aleut"),
+    code("alg", None, "Algonquian (Other)"),
+    code("alt", None, "Altai"),
+    code("amh", Some("am"), "Amharic"),
+    code("ang", None, "English, Old (ca. 450-1100)"),
+    code("anp", None, "Angika"),
+    code("apa", None, "Apache languages"),
+    code("ara", Some("ar"), "Arabic"),
+    code("arc", None, "Aramaic"),
+    code("arg", Some("an"), "Aragonese"),
+    code("arm", Some("hy"), "Armenian"),
+    code("arn", None, "Mapuche"),
+    code("arp", None, "Arapaho"),
+    code("art", None, "Artificial (Other)"),
+    code("arw", None, "Arawak"),
+    code("asm", Some("as"), "Assamese"),
+    code("ast", None, "Bable"),
+    code("ath", None, "Athapascan (Other)"),
+    code("aus", None, "Australian languages"),
+    code("ava", Some("av"), "Avaric"),
+    code("ave", Some("ae"), "Avestan"),
+    code("awa", None, "Awadhi"),
+    code("aym", Some("ay"), "Aymara"),
+    code("aze", Some("az"), "Azerbaijani"),
+    code("bad", None, "Banda languages"),
+    code("bai", None, "Bamileke languages"),
+    code("bak", Some("ba"), "Bashkir"),
+    code("bal", None, "Baluchi"),
+    code("bam", Some("bm"), "Bambara"),
+    code("ban", None, "Balinese"),
+    code("baq", Some("eu"), "Basque"),
+    code("bas", None, "Basa"),
+    code("bat", None, "Baltic (Other)"),
+    code("bej", None, "Beja"),
+    code("bel", Some("be"), "Belarusian"),
+    code("bem", None, "Bemba"),
+    code("ben", Some("bn"), "Bengali"),
+    code("ber", None, "Berber (Other)"),
+    code("bho", None, "Bhojpuri"),
+    code("bih", Some("bh"), "Bihari (Other)"),
+    code("bik", None, "Bikol"),
+    code("bin", None, "Edo"),
+    code("bis", Some("bi"), "Bislama"),
+    code("bla", None, "Siksika"),
+    code("bnt", None, "Bantu (Other)"),
+    code("bos", Some("bs"), "Bosnian"),
+    code("bra", None, "Braj"),
+    code("bre", Some("br"), "Breton"),
+    code("btk", None, "Batak"),
+    code("bua", None, "Buriat"),
+    code("bug", None, "Bugis"),
+    code("bul", Some("bg"), "Bulgarian"),
+    code("bur", Some("my"), "Burmese"),
+    code("byn", None, "Bilin"),
+    code("cad", None, "Caddo"),
+    code("cai", None, "Central American Indian (Other)"),
+    obsolete("cam", "khm", Some("km"), "Khmer"),
+    code("car", None, "Carib"),
+    code("cat", Some("ca"), "Catalan"),
+    code("cau", None, "Caucasian (Other)"),
+    code("ceb", None, "Cebuano"),
+    code("cel", None, "Celtic (Other)"),
+    code("cha", Some("ch"), "Chamorro"),
+    code("chb", None, "Chibcha"),
+    code("che", Some("ce"), "Chechen"),
+    code("chg", None, "Chagatai"),
+    code("chi", Some("zh"), "Chinese"),
+    code("chk", None, "Chuukese"),
+    code("chm", None, "Mari"),
+    code("chn", None, "Chinook jargon"),
+    code("cho", None, "Choctaw"),
+    code("chp", None, "Chipewyan"),
+    code("chr", None, "Cherokee"),
+    code("chu", Some("cu"), "Church Slavic"),
+    code("chv", Some("cv"), "Chuvash"),
+    code("chy", None, "Cheyenne"),
+    code("cmc", None, "Chamic languages"),
+    code("cnr", None, "Montenegrin"),
+    code("cop", None, "Coptic"),
+    code("cor", Some("kw"), "Cornish"),
+    code("cos", Some("co"), "Corsican"),
+    code("cpe", None, "Creoles and Pidgins, English-based (Other)"),
+    code("cpf", None, "Creoles and Pidgins, French-based (Other)"),
+    code("cpp", None, "Creoles and Pidgins, Portuguese-based (Other)"),
+    code("cre", Some("cr"), "Cree"),
+    code("crh", None, "Crimean Tatar"),
+    code("crp", None, "Creoles and Pidgins (Other)"),
+    code("csb", None, "Kashubian"),
+    code("cus", None, "Cushitic (Other)"),
+    code("cze", Some("cs"), "Czech"),
+    code("dak", None, "Dakota"),
+    code("dan", Some("da"), "Danish"),
+    code("dar", None, "Dargwa"),
+    code("day", None, "Dayak"),
+    code("del", None, "Delaware"),
+    code("den", None, "Slavey"),
+    code("dgr", None, "Dogrib"),
+    code("din", None, "Dinka"),
+    code("div", Some("dv"), "Divehi"),
+    code("doi", None, "Dogri"),
+    code("dra", None, "Dravidian (Other)"),
+    code("dsb", None, "Lower Sorbian"),
+    code("dua", None, "Duala"),
+    code("dum", None, "Dutch, Middle (ca. 1050-1350)"),
+    code("dut", Some("nl"), "Dutch"),
+    code("dyu", None, "Dyula"),
+    code("dzo", Some("dz"), "Dzongkha"),
+    code("efi", None, "Efik"),
+    code("egy", None, "Egyptian"),
+    code("eka", None, "Ekajuk"),
+    code("elx", None, "Elamite"),
+    code("eng", Some("en"), "English"),
+    code("enm", None, "English, Middle (1100-1500)"),
+    code("epo", Some("eo"), "Esperanto"),
+    obsolete("esp", "epo", Some("eo"), "Esperanto"),
+    code("est", Some("et"), "Estonian"),
+    obsolete("eth", "gez", None, "Ethiopic"),
+    code("ewe", Some("ee"), "Ewe"),
+    code("ewo", None, "Ewondo"),
+    code("fan", None, "Fang"),
+    code("fao", Some("fo"), "Faroese"),
+    obsolete("far", "fao", Some("fo"), "Faroese"),
+    code("fat", None, "Fanti"),
+    code("fij", Some("fj"), "Fijian"),
+    code("fil", None, "Filipino"),
+    code("fin", Some("fi"), "Finnish"),
+    code("fiu", None, "Finno-Ugrian (Other)"),
+    code("fon", None, "Fon"),
+    code("fre", Some("fr"), "French"),
+    obsolete("fri", "fry", Some("fy"), "Frisian"),
+    code("frm", None, "French, Middle (ca. 1300-1600)"),
+    code("fro", None, "French, Old (ca. 842-1300)"),
+    code("frr", None, "North Frisian"),
+    code("frs", None, "East Frisian"),
+    code("fry", Some("fy"), "Frisian"),
+    code("ful", Some("ff"), "Fula"),
+    code("fur", None, "Friulian"),
+    code("gaa", None, "Ga"),
+    obsolete("gae", "gla", Some("gd"), "Scottish Gaelic"),
+    obsolete("gag", "glg", Some("gl"), "Galician"),
+    code("gay", None, "Gayo"),
+    code("gba", None, "Gbaya"),
+    code("gem", None, "Germanic (Other)"),
+    code("geo", Some("ka"), "Georgian"),
+    code("ger", Some("de"), "German"),
+    code("gez", None, "Ethiopic"),
+    code("gil", None, "Gilbertese"),
+    code("gla", Some("gd"), "Scottish Gaelic"),
+    code("gle", Some("ga"), "Irish"),
+    code("glg", Some("gl"), "Galician"),
+    code("glv", Some("gv"), "Manx"),
+    code("gmh", None, "German, Middle High (ca. 1050-1500)"),
+    code("goh", None, "German, Old High (ca. 750-1050)"),
+    code("gon", None, "Gondi"),
+    code("gor", None, "Gorontalo"),
+    code("got", None, "Gothic"),
+    code("grb", None, "Grebo"),
+    code("grc", None, "Greek, Ancient (to 1453)"),
+    code("gre", Some("el"), "Greek, Modern (1453-)"),
+    code("grn", Some("gn"), "Guarani"),
+    code("gsw", None, "Swiss German"),
+    obsolete("gua", "grn", Some("gn"), "Guarani"),
+    code("guj", Some("gu"), "Gujarati"),
+    code("gwi", None, "Gwich'in"),
+    code("hai", None, "Haida"),
+    code("hat", Some("ht"), "Haitian French Creole"),
+    code("hau", Some("ha"), "Hausa"),
+    code("haw", None, "Hawaiian"),
+    code("heb", Some("he"), "Hebrew"),
+    code("her", Some("hz"), "Herero"),
+    code("hil", None, "Hiligaynon"),
+    code("him", None, "Western Pahari languages"),
+    code("hin", Some("hi"), "Hindi"),
+    code("hit", None, "Hittite"),
+    code("hmn", None, "Hmong"),
+    code("hmo", Some("ho"), "Hiri Motu"),
+    code("hrv", Some("hr"), "Croatian"),
+    code("hsb", None, "Upper Sorbian"),
+    code("hun", Some("hu"), "Hungarian"),
+    code("hup", None, "Hupa"),
+    code("iba", None, "Iban"),
+    code("ibo", Some("ig"), "Igbo"),
+    code("ice", Some("is"), "Icelandic"),
+    code("ido", Some("io"), "Ido"),
+    code("iii", Some("ii"), "Sichuan Yi"),
+    code("ijo", None, "Ijo"),
+    code("iku", Some("iu"), "Inuktitut"),
+    code("ile", Some("ie"), "Interlingue"),
+    code("ilo", None, "Iloko"),
+    code("ina", Some("ia"), "Interlingua (International Auxiliary Language Association)"),
+    code("inc", None, "Indic (Other)"),
+    code("ind", Some("id"), "Indonesian"),
+    code("ine", None, "Indo-European (Other)"),
+    code("inh", None, "Ingush"),
+    obsolete("int", "ina", Some("ia"), "Interlingua (International Auxiliary Language Association)"),
+    code("ipk", Some("ik"), "Inupiaq"),
+    code("ira", None, "Iranian (Other)"),
+    obsolete("iri", "gle", Some("ga"), "Irish"),
+    code("iro", None, "Iroquoian (Other)"),
+    code("ita", Some("it"), "Italian"),
+    code("jav", Some("jv"), "Javanese"),
+    code("jbo", None, "Lojban (Artificial language)"),
+    code("jpn", Some("ja"), "Japanese"),
+    code("jpr", None, "Judeo-Persian"),
+    code("jrb", None, "Judeo-Arabic"),
+    code("kaa", None, "Kara-Kalpak"),
+    code("kab", None, "Kabyle"),
+    code("kac", None, "Kachin"),
+    code("kal", Some("kl"), "Kalâtdlisut"),
+    code("kam", None, "Kamba"),
+    code("kan", Some("kn"), "Kannada"),
+    code("kar", None, "Karen languages"),
+    code("kas", Some("ks"), "Kashmiri"),
+    code("kau", Some("kr"), "Kanuri"),
+    code("kaw", None, "Kawi"),
+    code("kaz", Some("kk"), "Kazakh"),
+    code("kbd", None, "Kabardian"),
+    code("kha", None, "Khasi"),
+    code("khi", None, "Khoisan (Other)"),
+    code("khm", Some("km"), "Khmer"),
+    code("kho", None, "Khotanese"),
+    code("kik", Some("ki"), "Kikuyu"),
+    code("kin", Some("rw"), "Kinyarwanda"),
+    code("kir", Some("ky"), "Kyrgyz"),
+    code("kmb", None, "Kimbundu"),
+    code("kok", None, "Konkani"),
+    code("kom", Some("kv"), "Komi"),
+    code("kon", Some("kg"), "Kongo"),
+    code("kor", Some("ko"), "Korean"),
+    code("kos", None, "Kosraean"),
+    code("kpe", None, "Kpelle"),
+    code("krc", None, "Karachay-Balkar"),
+    code("krl", None, "Karelian"),
+    code("kro", None, "Kru (Other)"),
+    code("kru", None, "Kurukh"),
+    code("kua", Some("kj"), "Kuanyama"),
+    code("kum", None, "Kumyk"),
+    code("kur", Some("ku"), "Kurdish"),
+    obsolete("kus", "kos", None, "Kosraean"),
+    code("kut", None, "Kootenai"),
+    code("lad", None, "Ladino"),
+    code("lah", None, "Lahndā"),
+    code("lam", None, "Lamba (Zambia and Congo)"),
+    obsolete("lan", "oci", Some("oc"), "Occitan (post-1500)"),
+    code("lao", Some("lo"), "Lao"),
+    obsolete("lap", "smi", None, "Sami"),
+    code("lat", Some("la"), "Latin"),
+    code("lav", Some("lv"), "Latvian"),
+    code("lez", None, "Lezgian"),
+    code("lim", Some("li"), "Limburgish"),
+    code("lin", Some("ln"), "Lingala"),
+    code("lit", Some("lt"), "Lithuanian"),
+    code("lol", None, "Mongo-Nkundu"),
+    code("loz", None, "Lozi"),
+    code("ltz", Some("lb"), "Luxembourgish"),
+    code("lua", None, "Luba-Lulua"),
+    code("lub", Some("lu"), "Luba-Katanga"),
+    code("lug", Some("lg"), "Ganda"),
+    code("lui", None, "Luiseño"),
+    code("lun", None, "Lunda"),
+    code("luo", None, "Luo (Kenya and Tanzania)"),
+    code("lus", None, "Mizo"),
+    code("mac", Some("mk"), "Macedonian"),
+    code("mad", None, "Madurese"),
+    code("mag", None, "Magahi"),
+    code("mah", Some("mh"), "Marshallese"),
+    code("mai", None, "Maithili"),
+    code("mak", None, "Makasar"),
+    code("mal", Some("ml"), "Malayalam"),
+    code("man", None, "Mandingo"),
+    code("mao", Some("mi"), "Maori"),
+    code("map", None, "Austronesian (Other)"),
+    code("mar", Some("mr"), "Marathi"),
+    code("mas", None, "Maasai"),
+    obsolete("max", "glv", Some("gv"), "Manx"),
+    code("may", Some("ms"), "Malay"),
+    code("mdf", None, "Moksha"),
+    code("mdr", None, "Mandar"),
+    code("men", None, "Mende"),
+    code("mga", None, "Irish, Middle (ca. 1100-1550)"),
+    code("mic", None, "Micmac"),
+    code("min", None, "Minangkabau"),
+    code("mis", None, "Miscellaneous languages"),
+    code("mkh", None, "Mon-Khmer (Other)"),
+    obsolete("mla", "mlg", Some("mg"), "Malagasy"),
+    code("mlg", Some("mg"), "Malagasy"),
+    code("mlt", Some("mt"), "Maltese"),
+    code("mnc", None, "Manchu"),
+    code("mni", None, "Manipuri"),
+    code("mno", None, "Manobo languages"),
+    code("moh", None, "Mohawk"),
+    obsolete("mol", "rum", Some("ro"), "Moldavian"),
+    code("mon", Some("mn"), "Mongolian"),
+    code("mos", None, "Mooré"),
+    code("mul", None, "Multiple languages"),
+    code("mun", None, "Munda (Other)"),
+    code("mus", None, "Creek"),
+    code("mwl", None, "Mirandese"),
+    code("mwr", None, "Marwari"),
+    code("myn", None, "Mayan languages"),
+    code("myv", None, "Erzya"),
+    code("nah", None, "Nahuatl"),
+    code("nai", None, "North American Indian (Other)"),
+    code("nap", None, "Neapolitan Italian"),
+    code("nau", Some("na"), "Nauru"),
+    code("nav", Some("nv"), "Navajo"),
+    code("nbl", Some("nr"), "Ndebele (South Africa)"),
+    code("nde", Some("nd"), "Ndebele (Zimbabwe)"),
+    code("ndo", Some("ng"), "Ndonga"),
+    code("nds", None, "Low German"),
+    code("nep", Some("ne"), "Nepali"),
+    code("new", None, "Newari"),
+    code("nia", None, "Nias"),
+    code("nic", None, "Niger-Kordofanian (Other)"),
+    code("niu", None, "Niuean"),
+    code("nno", Some("nn"), "Norwegian (Nynorsk)"),
+    code("nob", Some("nb"), "Norwegian (Bokmål)"),
+    code("nog", None, "Nogai"),
+    code("non", None, "Old Norse"),
+    code("nor", Some("no"), "Norwegian"),
+    code("nqo", None, "N'Ko"),
+    code("nso", None, "Northern Sotho"),
+    code("nub", None, "Nubian languages"),
+    code("nwc", None, "Newari, Old"),
+    code("nya", Some("ny"), "Nyanja"),
+    code("nym", None, "Nyamwezi"),
+    code("nyn", None, "Nyankole"),
+    code("nyo", None, "Nyoro"),
+    code("nzi", None, "Nzima"),
+    code("oci", Some("oc"), "Occitan (post-1500)"),
+    code("oji", Some("oj"), "Ojibwa"),
+    code("ori", Some("or"), "Oriya"),
+    code("orm", Some("om"), "Oromo"),
+    code("osa", None, "Osage"),
+    code("oss", Some("os"), "Ossetic"),
+    code("ota", None, "Turkish, Ottoman"),
+    code("oto", None, "Otomian languages"),
+    code("paa", None, "Papuan (Other)"),
+    code("pag", None, "Pangasinan"),
+    code("pal", None, "Pahlavi"),
+    code("pam", None, "Pampanga"),
+    code("pan", Some("pa"), "Panjabi"),
+    code("pap", None, "Papiamento"),
+    code("pau", None, "Palauan"),
+    code("peo", None, "Old Persian (ca. 600-400 B.C.)"),
+    code("per", Some("fa"), "Persian"),
+    code("phi", None, "Philippine (Other)"),
+    code("phn", None, "Phoenician"),
+    code("pli", Some("pi"), "Pali"),
+    code("pol", Some("pl"), "Polish"),
+    code("pon", None, "Pohnpeian"),
+    code("por", Some("pt"), "Portuguese"),
+    code("pra", None, "Prakrit languages"),
+    code("pro", None, "Provençal (to 1500)"),
+    code("pus", Some("ps"), "Pushto"),
+    code("que", Some("qu"), "Quechua"),
+    code("raj", None, "Rajasthani"),
+    code("rap", None, "Rapanui"),
+    code("rar", None, "Rarotongan"),
+    code("roa", None, "Romance (Other)"),
+    code("roh", Some("rm"), "Raeto-Romance"),
+    code("rom", None, "Romani"),
+    code("rum", Some("ro"), "Romanian"),
+    code("run", Some("rn"), "Rundi"),
+    code("rup", None, "Aromanian"),
+    code("rus", Some("ru"), "Russian"),
+    code("sad", None, "Sandawe"),
+    code("sag", Some("sg"), "Sango (Ubangi Creole)"),
+    code("sah", None, "Yakut"),
+    code("sai", None, "South American Indian (Other)"),
+    code("sal", None, "Salishan languages"),
+    code("sam", None, "Samaritan Aramaic"),
+    code("san", Some("sa"), "Sanskrit"),
+    obsolete("sao", "smo", Some("sm"), "Samoan"),
+    code("sas", None, "Sasak"),
+    code("sat", None, "Santali"),
+    obsolete("scc", "srp", Some("sr"), "Serbian"),
+    code("scn", None, "Sicilian Italian"),
+    code("sco", None, "Scots"),
+    obsolete("scr", "hrv", Some("hr"), "Croatian"),
+    code("sel", None, "Selkup"),
+    code("sem", None, "Semitic (Other)"),
+    code("sga", None, "Irish, Old (to 1100)"),
+    code("sgn", None, "Sign languages"),
+    code("shn", None, "Shan"),
+    obsolete("sho", "sna", Some("sn"), "Shona"),
+    code("sid", None, "Sidamo"),
+    code("sin", Some("si"), "Sinhalese"),
+    code("sio", None, "Siouan (Other)"),
+    code("sit", None, "Sino-Tibetan (Other)"),
+    code("sla", None, "Slavic (Other)"),
+    code("slo", Some("sk"), "Slovak"),
+    code("slv", Some("sl"), "Slovenian"),
+    code("sma", None, "Southern Sami"),
+    code("sme", Some("se"), "Northern Sami"),
+    code("smi", None, "Sami"),
+    code("smj", None, "Lule Sami"),
+    code("smn", None, "Inari Sami"),
+    code("smo", Some("sm"), "Samoan"),
+    code("sms", None, "Skolt Sami"),
+    code("sna", Some("sn"), "Shona"),
+    code("snd", Some("sd"), "Sindhi"),
+    obsolete("snh", "sin", Some("si"), "Sinhalese"),
+    code("snk", None, "Soninke"),
+    code("sog", None, "Sogdian"),
+    code("som", Some("so"), "Somali"),
+    code("son", None, "Songhai"),
+    code("sot", Some("st"), "Sotho"),
+    code("spa", Some("es"), "Spanish"),
+    code("srd", Some("sc"), "Sardinian"),
+    code("srn", None, "Sranan"),
+    code("srp", Some("sr"), "Serbian"),
+    code("srr", None, "Serer"),
+    code("ssa", None, "Nilo-Saharan (Other)"),
+    obsolete("sso", "sot", Some("st"), "Sotho"),
+    code("ssw", Some("ss"), "Swazi"),
+    code("suk", None, "Sukuma"),
+    code("sun", Some("su"), "Sundanese"),
+    code("sus", None, "Susu"),
+    code("sux", None, "Sumerian"),
+    code("swa", Some("sw"), "Swahili"),
+    code("swe", Some("sv"), "Swedish"),
+    obsolete("swz", "ssw", Some("ss"), "Swazi"),
+    code("syc", None, "Syriac"),
+    code("syr", None, "Syriac, Modern"),
+    obsolete("tag", "tgl", Some("tl"), "Tagalog"),
+    code("tah", Some("ty"), "Tahitian"),
+    code("tai", None, "Tai (Other)"),
+    obsolete("taj", "tgk", Some("tg"), "Tajik"),
+    code("tam", Some("ta"), "Tamil"),
+    obsolete("tar", "tat", Some("tt"), "Tatar"),
+    code("tat", Some("tt"), "Tatar"),
+    code("tel", Some("te"), "Telugu"),
+    code("tem", None, "Temne"),
+    code("ter", None, "Terena"),
+    code("tet", None, "Tetum"),
+    code("tgk", Some("tg"), "Tajik"),
+    code("tgl", Some("tl"), "Tagalog"),
+    code("tha", Some("th"), "Thai"),
+    code("tib", Some("bo"), "Tibetan"),
+    code("tig", None, "Tigré"),
+    code("tir", Some("ti"), "Tigrinya"),
+    code("tiv", None, "Tiv"),
+    code("tkl", None, "Tokelauan"),
+    code("tlh", None, "Klingon (Artificial language)"),
+    code("tli", None, "Tlingit"),
+    code("tmh", None, "Tamashek"),
+    code("tog", None, "Tonga (Nyasa)"),
+    code("ton", Some("to"), "Tongan"),
+    code("tpi", None, "Tok Pisin"),
+    obsolete("tru", "chk", None, "Truk"),
+    code("tsi", None, "Tsimshian"),
+    code("tsn", Some("tn"), "Tswana"),
+    code("tso", Some("ts"), "Tsonga"),
+    obsolete("tsw", "tsn", Some("tn"), "Tswana"),
+    code("tuk", Some("tk"), "Turkmen"),
+    code("tum", None, "Tumbuka"),
+    code("tup", None, "Tupi languages"),
+    code("tur", Some("tr"), "Turkish"),
+    code("tut", None, "Altaic (Other)"),
+    code("tvl", None, "Tuvaluan"),
+    code("twi", Some("tw"), "Twi"),
+    code("tyv", None, "Tuvinian"),
+    code("udm", None, "Udmurt"),
+    code("uga", None, "Ugaritic"),
+    code("uig", Some("ug"), "Uighur"),
+    code("ukr", Some("uk"), "Ukrainian"),
+    code("umb", None, "Umbundu"),
+    code("und", None, "Undetermined"),
+    code("urd", Some("ur"), "Urdu"),
+    code("uzb", Some("uz"), "Uzbek"),
+    code("vai", None, "Vai"),
+    code("ven", Some("ve"), "Venda"),
+    code("vie", Some("vi"), "Vietnamese"),
+    code("vol", Some("vo"), "Volapük"),
+    code("vot", None, "Votic"),
+    code("wak", None, "Wakashan languages"),
+    code("wal", None, "Wolayta"),
+    code("war", None, "Waray"),
+    code("was", None, "Washoe"),
+    code("wel", Some("cy"), "Welsh"),
+    code("wen", None, "Sorbian (Other)"),
+    code("wln", Some("wa"), "Walloon"),
+    code("wol", Some("wo"), "Wolof"),
+    code("xal", None, "Oirat"),
+    code("xho", Some("xh"), "Xhosa"),
+    code("yao", None, "Yao (Africa)"),
+    code("yap", None, "Yapese"),
+    code("yid", Some("yi"), "Yiddish"),
+    code("yor", Some("yo"), "Yoruba"),
+    code("ypk", None, "Yupik languages"),
+    code("zap", None, "Zapotec"),
+    code("zbl", None, "Blissymbolics"),
+    code("zen", None, "Zenaga"),
+    code("zha", Some("za"), "Zhuang"),
+    code("znd", None, "Zande languages"),
+    code("zul", Some("zu"), "Zulu"),
+    code("zun", None, "Zuni"),
+    code("zxx", None, "No linguistic content"),
+    code("zza", None, "Zaza"),
+];
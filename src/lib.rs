@@ -1,23 +1,49 @@
 mod client;
+mod covers;
 mod edition;
 mod work;
 mod author;
 mod entity;
+mod date;
+mod dump;
+mod catalog;
+mod ratings;
+mod bookshelves;
+mod list;
+mod reading_log;
+mod session;
+mod conflict;
+mod olid;
+mod isbn;
+mod read;
+mod archive;
+mod language;
+mod physical;
+mod canonical;
+mod name;
+mod bibtex;
+mod csl;
+mod xml;
+mod marc;
+mod marc8;
+mod dublin_core;
+mod mods;
+mod ris;
+mod json_ld;
+mod onix;
+mod opds;
 
 pub use client::Client;
-pub use client::CoverSize;
 pub use client::QueryType;
-pub use client::CoverKey;
+pub use covers::CoverSize;
+pub use covers::CoverKey;
 
 pub use edition::Edition;
 pub use edition::ByStatement;
-pub use edition::Created;
 pub use edition::EditionName;
 pub use edition::Identifiers;
 pub use edition::Language;
-pub use edition::LastModified;
 pub use edition::NumberOfPages;
-pub use edition::PhysicalDimensions;
 pub use edition::PhysicalFormat;
 pub use edition::PublishDate;
 pub use edition::Revision;
@@ -25,8 +51,8 @@ pub use edition::Subtitle;
 pub use edition::TableOfContents;
 pub use edition::Title;
 pub use edition::Type;
-pub use edition::Weight;
 pub use edition::Authors as AuthorsEdition;
+pub use edition::Link as LinkEdition;
 pub use edition::Works;
 pub use edition::Note;
 pub use edition::Notes;
@@ -38,3 +64,99 @@ pub use work::Author as AuthorWork;
 pub use author::Author;
 
 pub use entity::Entity;
+
+pub use date::Created;
+pub use date::Datetime;
+pub use date::LastModified;
+pub use date::PartialDate;
+
+pub use dump::Reader as DumpReader;
+pub use dump::Record as DumpRecord;
+pub use dump::Error as DumpError;
+pub use dump::ErrorKind as DumpErrorKind;
+
+pub use catalog::Catalog;
+pub use catalog::Scheme as CatalogScheme;
+pub use catalog::Import as CatalogImport;
+pub use catalog::Error as CatalogError;
+
+pub use ratings::Ratings;
+pub use ratings::Summary as RatingsSummary;
+pub use ratings::Counts as RatingsCounts;
+
+pub use bookshelves::Bookshelves;
+
+pub use list::List;
+pub use list::ListSummary;
+pub use list::ListSubjects;
+pub use list::Links;
+pub use list::Page;
+pub use list::Paging;
+pub use list::Picture;
+pub use list::Seed;
+pub use list::SeedEntry;
+pub use list::SubjectCount;
+
+pub use reading_log::Shelf;
+pub use reading_log::ReadingLog;
+pub use reading_log::ReadingLogEntry;
+pub use reading_log::ReadingLogWork;
+pub use reading_log::ReadingLogAuthor;
+
+pub use session::Credentials;
+pub use session::Session;
+
+pub use conflict::Conflict;
+pub use conflict::MergeConflict;
+pub use conflict::SaveError;
+
+pub use olid::Olid;
+pub use olid::InvalidOlid;
+
+pub use read::Availability;
+pub use read::MatchType;
+pub use read::ReadCover;
+pub use read::ReadItem;
+pub use read::ReadKey;
+pub use read::ReadRecord;
+pub use read::ReadStatus;
+
+pub use archive::Archive;
+pub use archive::ArchiveFile;
+pub use archive::ArchiveItem;
+
+pub use language::LanguageCode;
+pub use language::LanguageRecord;
+
+pub use physical::Dimensions;
+pub use physical::LengthUnit;
+pub use physical::Pagination;
+pub use physical::Weight;
+
+pub use canonical::canonicalize;
+pub use canonical::to_canonical_json;
+pub use canonical::Elided;
+
+pub use bibtex::write_bibliography as write_bibtex;
+
+pub use csl::Item as CslItem;
+pub use csl::Name as CslName;
+pub use csl::Date as CslDate;
+pub use csl::Style as CitationStyle;
+
+pub use marc::Record as MarcRecord;
+pub use marc::ControlField as MarcControlField;
+pub use marc::DataField as MarcDataField;
+pub use marc::Subfield as MarcSubfield;
+pub use marc::Error as MarcError;
+pub use marc::SourceRecord as MarcSourceRecord;
+pub use marc::RawMarc;
+pub use marc::parse_marc21;
+pub use marc::parse_marcxml;
+pub use marc::write_marcxml;
+
+pub use ris::write_ris;
+
+pub use onix::write_onix;
+
+pub use opds::Feed as OpdsFeed;
@@ -0,0 +1,215 @@
+use crate::client::process_olid_key;
+use crate::date::{Created, LastModified};
+use crate::edition::Type;
+use crate::olid::Olid;
+
+// Query parameters shared by the paged list endpoints
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Paging {
+    pub limit: usize,
+    pub offset: usize,
+}
+
+impl Default for Paging {
+    fn default() -> Self {
+        Self {
+            limit: 50,
+            offset: 0,
+        }
+    }
+}
+
+impl Paging {
+    pub fn new(limit: usize, offset: usize) -> Self {
+        Self { limit, offset }
+    }
+
+    pub fn next(&self) -> Self {
+        Self {
+            limit: self.limit,
+            offset: self.offset + self.limit,
+        }
+    }
+
+    pub(crate) fn query(&self) -> String {
+        format!("limit={}&offset={}", self.limit, self.offset)
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct Links {
+    #[serde(rename = "self")]
+    pub self_field: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prev: Option<String>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct Page<T> {
+    #[serde(default)]
+    pub links: Links,
+    pub size: usize,
+    pub entries: Vec<T>,
+}
+
+impl<T> Page<T> {
+    pub fn has_next(&self) -> bool {
+        self.links.next.is_some()
+    }
+}
+
+// https://openlibrary.org/people/george08/lists.json
+#[derive(Default, Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct ListSummary {
+    pub url: String,
+    pub full_url: String,
+    pub name: String,
+    pub seed_count: usize,
+    pub edition_count: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_update: Option<String>,
+}
+
+// https://openlibrary.org/people/george08/lists/OL97L.json
+#[derive(Default, Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct List {
+    pub key: String,
+    pub name: String,
+    #[serde(rename = "type")]
+    pub type_field: Type,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub seeds: Vec<Seed>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revision: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latest_revision: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created: Option<Created>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_modified: Option<LastModified>,
+}
+
+impl List {
+    pub fn get_olid(&self) -> String {
+        process_olid_key(&self.key)
+    }
+}
+
+// Editions, works and authors are stored as {"key": ...}, subjects as plain
+// strings like "subject:love", "place:london", "person:..." or "time:..."
+#[derive(Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
+#[serde(untagged)]
+pub enum Seed {
+    Thing(Type),
+    Subject(String),
+}
+
+impl Seed {
+    pub fn subject(subject: &str) -> Self {
+        Seed::Subject(format!("subject:{}", subject))
+    }
+
+    pub fn place(place: &str) -> Self {
+        Seed::Subject(format!("place:{}", place))
+    }
+
+    pub fn person(person: &str) -> Self {
+        Seed::Subject(format!("person:{}", person))
+    }
+
+    pub fn time(time: &str) -> Self {
+        Seed::Subject(format!("time:{}", time))
+    }
+
+    pub fn key(&self) -> &str {
+        match self {
+            Seed::Thing(thing) => &thing.key,
+            Seed::Subject(subject) => subject,
+        }
+    }
+}
+
+impl From<Olid> for Seed {
+    fn from(olid: Olid) -> Self {
+        Seed::Thing(Type { key: olid.key() })
+    }
+}
+
+// Request bodies for the list write endpoints
+#[derive(serde_derive::Serialize)]
+pub(crate) struct NewList<'a> {
+    pub name: &'a str,
+    pub description: &'a str,
+    pub tags: Vec<String>,
+    pub seeds: &'a [Seed],
+}
+
+#[derive(serde_derive::Serialize)]
+pub(crate) struct SeedChanges<'a> {
+    pub add: &'a [Seed],
+    pub remove: &'a [Seed],
+}
+
+#[derive(serde_derive::Deserialize)]
+pub(crate) struct NewListResponse {
+    pub key: String,
+}
+
+// https://openlibrary.org/people/george08/lists/OL97L/seeds.json
+#[derive(Default, Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct SeedEntry {
+    pub url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub full_url: Option<String>,
+    #[serde(rename = "type")]
+    pub type_field: String,
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub work_count: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub edition_count: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ebook_count: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_update: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub picture: Option<Picture>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct Picture {
+    pub url: String,
+}
+
+// https://openlibrary.org/people/george08/lists/OL97L/subjects.json
+#[derive(Default, Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct ListSubjects {
+    #[serde(default)]
+    pub subjects: Vec<SubjectCount>,
+    #[serde(default)]
+    pub places: Vec<SubjectCount>,
+    #[serde(default)]
+    pub people: Vec<SubjectCount>,
+    #[serde(default)]
+    pub times: Vec<SubjectCount>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct SubjectCount {
+    pub name: String,
+    pub count: usize,
+    pub url: String,
+}
+
+// Accepts "/people/george08/lists/OL97L", "people/george08/lists/OL97L.json" or a full_url
+// with the trailing list name and returns "/people/george08/lists/OL97L"
+pub(crate) fn list_path(list_key: &str) -> String {
+    let key = list_key.trim_start_matches('/').trim_end_matches(".json");
+    let parts: Vec<&str> = key.split('/').take(4).collect();
+
+    format!("/{}", parts.join("/"))
+}
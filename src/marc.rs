@@ -0,0 +1,978 @@
+use std::error;
+use std::fmt;
+use std::io::{self, Write};
+
+use chrono::{NaiveDate, NaiveDateTime};
+use quick_xml::events::{BytesStart, Event};
+
+use crate::author::Author;
+use crate::date::{Datetime, PartialDate};
+use crate::edition::{Edition, Language, Note, TableOfContents, Type};
+use crate::entity::Entity;
+use crate::marc8;
+use crate::physical::Pagination;
+use crate::xml;
+
+const MARCXML_NAMESPACE: &str = "http://www.loc.gov/MARC21/slim";
+const MARCXML_SCHEMA_LOCATION: &str = "http://www.loc.gov/MARC21/slim http://www.loc.gov/standards/marcxml/schema/MARC21slim.xsd";
+
+const SUBFIELD_DELIMITER: u8 = 0x1F;
+const FIELD_TERMINATOR: u8 = 0x1E;
+const RECORD_TERMINATOR: u8 = 0x1D;
+
+const LEADER_LENGTH: usize = 24;
+const DIRECTORY_ENTRY_LENGTH: usize = 12;
+// Record length and field lengths are five and four digit numbers in the leader and directory
+const MAX_RECORD_LENGTH: usize = 99_999;
+const MAX_FIELD_LENGTH: usize = 9_999;
+
+// New record, language material, monograph, UCS/Unicode, encoding level unknown,
+// ISBD punctuation omitted. Lengths and the base address are filled in when writing.
+const LEADER_TEMPLATE: &str = "00000nam a2200000uc 4500";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ControlField {
+    pub tag: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Subfield {
+    pub code: char,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DataField {
+    pub tag: String,
+    pub ind1: char,
+    pub ind2: char,
+    pub subfields: Vec<Subfield>,
+}
+
+impl DataField {
+    pub fn new(tag: &str, ind1: char, ind2: char) -> Self {
+        Self {
+            tag: tag.to_string(),
+            ind1,
+            ind2,
+            subfields: Vec::new(),
+        }
+    }
+
+    pub fn subfield(mut self, code: char, value: &str) -> Self {
+        self.subfields.push(Subfield {
+            code,
+            value: value.to_string(),
+        });
+        self
+    }
+
+    // The first value of a subfield, e.g. field.get('a')
+    pub fn get(&self, code: char) -> Option<&str> {
+        self.subfields.iter().find(|subfield| subfield.code == code).map(|subfield| subfield.value.as_str())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    pub leader: String,
+    pub control_fields: Vec<ControlField>,
+    pub data_fields: Vec<DataField>,
+}
+
+impl Default for Record {
+    fn default() -> Self {
+        Self {
+            leader: String::from(LEADER_TEMPLATE),
+            control_fields: Vec::new(),
+            data_fields: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    RecordTooLong(usize),
+    FieldTooLong(String),
+    // ISO 2709 data whose leader or directory does not add up
+    Malformed(String),
+    // Leader position 9 names an encoding other than UTF-8 or MARC-8, or MARC-8 switches to a
+    // character set that is not read
+    UnsupportedEncoding(String),
+    Xml(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::RecordTooLong(length) => write!(f, "MARC record of {} bytes exceeds {} bytes", length, MAX_RECORD_LENGTH),
+            Error::FieldTooLong(tag) => write!(f, "MARC field {} exceeds {} bytes", tag, MAX_FIELD_LENGTH),
+            Error::Malformed(message) => write!(f, "malformed MARC record: {}", message),
+            Error::UnsupportedEncoding(message) => write!(f, "unsupported MARC character encoding: {}", message),
+            Error::Xml(message) => write!(f, "invalid MARCXML: {}", message),
+        }
+    }
+}
+
+impl error::Error for Error {}
+
+impl Record {
+    pub fn control(&mut self, tag: &str, value: &str) {
+        self.control_fields.push(ControlField {
+            tag: tag.to_string(),
+            value: value.to_string(),
+        });
+    }
+
+    // Fields without subfields are not written, so optional values can be added unconditionally
+    pub fn data(&mut self, field: DataField) {
+        if !field.subfields.is_empty() {
+            self.data_fields.push(field);
+        }
+    }
+
+    pub fn control_field(&self, tag: &str) -> Option<&str> {
+        self.control_fields.iter().find(|field| field.tag == tag).map(|field| field.value.as_str())
+    }
+
+    pub fn fields(&self, tag: &str) -> impl Iterator<Item = &DataField> {
+        let tag = tag.to_string();
+        self.data_fields.iter().filter(move |field| field.tag == tag)
+    }
+
+    pub fn from_marc21(bytes: &[u8]) -> Result<Self, Error> {
+        let leader = bytes.get(..LEADER_LENGTH).ok_or_else(|| malformed("record is shorter than its leader"))?;
+        let base_address = number(&leader[12..17], "base address")?;
+        let directory_end = bytes[LEADER_LENGTH..]
+            .iter()
+            .position(|b| *b == FIELD_TERMINATOR)
+            .map(|position| LEADER_LENGTH + position)
+            .ok_or_else(|| malformed("directory is not terminated"))?;
+        let data = bytes.get(base_address..).ok_or_else(|| malformed("base address is past the end of the record"))?;
+        // a is UCS/Unicode, blank is MARC-8
+        let marc8 = match leader[9] {
+            b'a' => false,
+            b' ' => true,
+            other => return Err(Error::UnsupportedEncoding(format!("leader position 9 is {:?}", other as char))),
+        };
+        let decode = |field: &[u8]| if marc8 { marc8::decode(field) } else { Ok(String::from_utf8_lossy(field).into_owned()) };
+
+        let mut record = Record {
+            leader: String::from_utf8_lossy(leader).into_owned(),
+            ..Record::default()
+        };
+
+        for entry in bytes[LEADER_LENGTH..directory_end].chunks(DIRECTORY_ENTRY_LENGTH) {
+            if entry.len() != DIRECTORY_ENTRY_LENGTH {
+                return Err(malformed("directory length is not a multiple of 12"));
+            }
+
+            let tag = String::from_utf8_lossy(&entry[0..3]).into_owned();
+            let length = number(&entry[3..7], "field length")?;
+            let start = number(&entry[7..12], "field start")?;
+            let field = data
+                .get(start..start + length)
+                .ok_or_else(|| malformed(&format!("field {} is past the end of the record", tag)))?;
+            let field = field.strip_suffix(&[FIELD_TERMINATOR]).unwrap_or(field);
+
+            if tag.starts_with("00") {
+                record.control(&tag, &decode(field)?);
+                continue;
+            }
+
+            let text = decode(field)?;
+            let mut parts = text.split(SUBFIELD_DELIMITER as char);
+            let mut indicators = parts.next().unwrap_or_default().chars();
+            let mut data_field = DataField::new(&tag, indicators.next().unwrap_or(' '), indicators.next().unwrap_or(' '));
+            for part in parts {
+                let mut chars = part.chars();
+                if let Some(code) = chars.next() {
+                    data_field = data_field.subfield(code, chars.as_str());
+                }
+            }
+            record.data_fields.push(data_field);
+        }
+
+        Ok(record)
+    }
+
+    // The first record of a MARCXML document
+    pub fn from_marcxml(text: &str) -> Result<Self, Error> {
+        parse_marcxml(text)?.into_iter().next().ok_or_else(|| Error::Xml(String::from("no record element")))
+    }
+
+    // Names from the 100/110 main entry and the 700/710 added entries
+    pub fn author_names(&self) -> Vec<String> {
+        ["100", "110", "700", "710"]
+            .iter()
+            .flat_map(|tag| self.fields(tag))
+            .filter_map(|field| field.get('a'))
+            .map(|name| trim_punctuation(name).to_string())
+            .collect()
+    }
+
+    // Maps the fields written by to_marc back, and what other catalogs commonly put in them.
+    // Author names have no Open Library keys, the 245 statement of responsibility or the main
+    // entry becomes by_statement and added entries contributions.
+    pub fn to_edition(&self) -> Edition {
+        let mut edition = Edition {
+            type_field: Type { key: String::from("/type/edition") },
+            ..Edition::default()
+        };
+
+        if let Some(olid) = self.control_field("001").map(str::trim).filter(|olid| olid.starts_with("OL") && olid.ends_with('M')) {
+            edition.key = format!("/books/{}", olid);
+        }
+
+        let fixed = self.control_field("008").unwrap_or_default();
+        let created = fixed
+            .get(0..6)
+            .and_then(|date| NaiveDate::parse_from_str(date, "%y%m%d").ok())
+            .and_then(|date| date.and_hms_opt(0, 0, 0));
+        let modified = self
+            .control_field("005")
+            .and_then(|value| NaiveDateTime::parse_from_str(value.trim(), "%Y%m%d%H%M%S%.f").ok());
+        edition.created = created.or(modified).map(Datetime::new).unwrap_or_default();
+        edition.last_modified = modified.or(created).map(Datetime::new).unwrap_or_default();
+
+        edition.publish_country = fixed.get(15..18).map(str::trim).filter(|place| !place.is_empty() && !place.contains('|')).map(String::from);
+
+        let mut languages: Vec<String> = fixed
+            .get(35..38)
+            .filter(|code| code.chars().all(|c| c.is_ascii_lowercase()))
+            .map(String::from)
+            .into_iter()
+            .collect();
+        for code in self.fields("041").flat_map(|field| field.subfields.iter()).filter(|subfield| subfield.code == 'a') {
+            if !languages.contains(&code.value) {
+                languages.push(code.value.clone());
+            }
+        }
+        edition.languages = non_empty(languages.into_iter().map(|code| Language { key: format!("/languages/{}", code) }).collect());
+
+        edition.lccn = non_empty(self.values("010", 'a').map(|lccn| lccn.trim().to_string()).collect());
+
+        let mut isbn10 = Vec::new();
+        let mut isbn13 = Vec::new();
+        let mut invalid = Vec::new();
+        for isbn in self.values("020", 'a') {
+            // "0141439513 (pbk.)"
+            let digits: String = isbn.split_whitespace().next().unwrap_or_default().chars().filter(|c| c.is_ascii_alphanumeric()).collect();
+            match digits.len() {
+                10 => isbn10.push(digits),
+                13 => isbn13.push(digits),
+                _ => invalid.push(isbn.to_string()),
+            }
+        }
+        edition.isbn10 = non_empty(isbn10);
+        edition.isbn13 = non_empty(isbn13);
+        edition.isbn_invalid = non_empty(invalid);
+
+        // (OCoLC)ocm12345678, (OCoLC)ocn123456789 and (OCoLC)on1234567890
+        edition.oclc_numbers = non_empty(
+            self.values("035", 'a')
+                .filter_map(|value| value.strip_prefix("(OCoLC)"))
+                .map(|number| number.trim_start_matches(|c: char| c.is_ascii_alphabetic()).to_string())
+                .collect(),
+        );
+
+        edition.lc_classifications = non_empty(
+            self.fields("050")
+                .filter_map(|field| {
+                    let class = field.get('a')?;
+                    Some(match field.get('b') {
+                        Some(item) => format!("{} {}", class, item),
+                        None => class.to_string(),
+                    })
+                })
+                .collect(),
+        );
+        edition.dewey_decimal_class = non_empty(self.values("082", 'a').map(String::from).collect());
+
+        if let Some(title) = self.fields("245").next() {
+            let parts: Vec<&str> = title
+                .subfields
+                .iter()
+                .filter(|subfield| matches!(subfield.code, 'a' | 'n' | 'p'))
+                .map(|subfield| trim_punctuation(&subfield.value))
+                .collect();
+            edition.title = parts.join(". ");
+            edition.subtitle = title.get('b').map(|subtitle| trim_punctuation(subtitle).to_string());
+            edition.by_statement = title.get('c').map(|statement| trim_punctuation(statement).to_string());
+        }
+        if edition.by_statement.is_none() {
+            edition.by_statement = ["100", "110"].iter().flat_map(|tag| self.values(tag, 'a')).next().map(|name| trim_punctuation(name).to_string());
+        }
+        edition.contributions = non_empty(
+            ["700", "710"]
+                .iter()
+                .flat_map(|tag| self.values(tag, 'a'))
+                .map(|name| trim_punctuation(name).to_string())
+                .collect(),
+        );
+
+        edition.edition_name = self.values("250", 'a').next().map(|name| trim_punctuation(name).to_string());
+
+        // 264 with second indicator 1 is the publication statement, older records use 260
+        let publication: Vec<&DataField> = self
+            .fields("264")
+            .filter(|field| field.ind2 == '1')
+            .chain(self.fields("260"))
+            .collect();
+        let subfields = |code: char| -> Vec<String> {
+            publication
+                .iter()
+                .flat_map(|field| field.subfields.iter())
+                .filter(|subfield| subfield.code == code)
+                .map(|subfield| trim_punctuation(&subfield.value).trim_matches(|c| c == '[' || c == ']').to_string())
+                .filter(|value| !value.is_empty())
+                .collect()
+        };
+        edition.publish_places = non_empty(subfields('a'));
+        edition.publishers = non_empty(subfields('b'));
+        edition.publish_date = subfields('c').into_iter().next().or_else(|| {
+            fixed.get(7..11).filter(|year| year.chars().all(|c| c.is_ascii_digit())).map(String::from)
+        });
+
+        if let Some(description) = self.fields("300").next() {
+            edition.pagination = description.get('a').map(|extent| trim_punctuation(extent).to_string());
+            edition.physical_dimensions = description.get('c').map(|dimensions| trim_punctuation(dimensions).to_string());
+            edition.number_of_pages = edition
+                .pagination
+                .as_deref()
+                .and_then(Pagination::parse)
+                .map(|pagination| pagination.body as usize)
+                .filter(|pages| *pages > 0);
+        }
+
+        let series: Vec<String> = ["490", "440", "830"]
+            .iter()
+            .flat_map(|tag| self.values(tag, 'a'))
+            .map(|series| trim_punctuation(series).to_string())
+            .collect();
+        let mut unique_series: Vec<String> = Vec::new();
+        for series in series {
+            if !unique_series.contains(&series) {
+                unique_series.push(series);
+            }
+        }
+        edition.series = non_empty(unique_series);
+
+        edition.table_of_contents = non_empty(
+            self.values("505", 'a')
+                .flat_map(|contents| contents.split("--"))
+                .map(|title| trim_punctuation(title).to_string())
+                .filter(|title| !title.is_empty())
+                .map(|title| TableOfContents {
+                    type_field: Type { key: String::from("/type/toc_item") },
+                    level: 0,
+                    title,
+                    ..TableOfContents::default()
+                })
+                .collect(),
+        );
+
+        edition.subjects = non_empty(
+            self.data_fields
+                .iter()
+                .filter(|field| matches!(field.tag.as_str(), "600" | "610" | "611" | "630" | "650" | "651"))
+                .map(|field| {
+                    // Topical, form, chronological and geographic subdivisions
+                    let parts: Vec<&str> = field
+                        .subfields
+                        .iter()
+                        .filter(|subfield| matches!(subfield.code, 'a' | 'x' | 'v' | 'y' | 'z'))
+                        .map(|subfield| trim_punctuation(&subfield.value))
+                        .collect();
+                    parts.join(" -- ")
+                })
+                .filter(|subject| !subject.is_empty())
+                .collect(),
+        );
+
+        edition.description = self.values("520", 'a').next().map(|summary| Note::NoteString(summary.trim().to_string()));
+        let notes: Vec<&str> = self.values("500", 'a').map(str::trim).collect();
+        if !notes.is_empty() {
+            edition.notes = Some(Note::NoteString(notes.join("\n")));
+        }
+
+        edition
+    }
+
+    fn values<'a>(&'a self, tag: &str, code: char) -> impl Iterator<Item = &'a str> {
+        self.fields(tag)
+            .flat_map(|field| field.subfields.iter())
+            .filter(move |subfield| subfield.code == code)
+            .map(|subfield| subfield.value.as_str())
+    }
+
+    // ISO 2709 with UTF-8 data
+    pub fn to_marc21(&self) -> Result<Vec<u8>, Error> {
+        let mut directory = Vec::new();
+        let mut data = Vec::new();
+
+        let fields = self
+            .control_fields
+            .iter()
+            .map(|field| (field.tag.as_str(), encode_control(field)))
+            .chain(self.data_fields.iter().map(|field| (field.tag.as_str(), encode_data(field))));
+
+        for (tag, bytes) in fields {
+            if bytes.len() > MAX_FIELD_LENGTH {
+                return Err(Error::FieldTooLong(tag.to_string()));
+            }
+            directory.extend_from_slice(format!("{:0>3.3}{:04}{:05}", tag, bytes.len(), data.len()).as_bytes());
+            data.extend_from_slice(&bytes);
+        }
+        directory.push(FIELD_TERMINATOR);
+
+        let base_address = LEADER_LENGTH + directory.len();
+        let record_length = base_address + data.len() + 1;
+        if record_length > MAX_RECORD_LENGTH {
+            return Err(Error::RecordTooLong(record_length));
+        }
+
+        let mut record = Vec::with_capacity(record_length);
+        record.extend_from_slice(self.leader_with(record_length, base_address).as_bytes());
+        record.extend_from_slice(&directory);
+        record.extend_from_slice(&data);
+        record.push(RECORD_TERMINATOR);
+
+        debug_assert_eq!(record.len(), record_length);
+
+        Ok(record)
+    }
+
+    pub fn to_marcxml(&self) -> String {
+        let mut writer = xml::Writer::new();
+        self.write_xml(&mut writer, true);
+
+        writer.finish()
+    }
+
+    fn write_xml(&self, writer: &mut xml::Writer, namespace: bool) {
+        if namespace {
+            writer.start(
+                "record",
+                &[
+                    ("xmlns", MARCXML_NAMESPACE),
+                    ("xmlns:xsi", "http://www.w3.org/2001/XMLSchema-instance"),
+                    ("xsi:schemaLocation", MARCXML_SCHEMA_LOCATION),
+                ],
+            );
+        } else {
+            writer.start("record", &[]);
+        }
+
+        writer.element("leader", &[], &self.leader);
+        for field in self.control_fields.iter() {
+            writer.element("controlfield", &[("tag", &field.tag)], &field.value);
+        }
+        for field in self.data_fields.iter() {
+            let (ind1, ind2) = (field.ind1.to_string(), field.ind2.to_string());
+            writer.start("datafield", &[("tag", &field.tag), ("ind1", &ind1), ("ind2", &ind2)]);
+            for subfield in field.subfields.iter() {
+                writer.element("subfield", &[("code", &subfield.code.to_string())], &subfield.value);
+            }
+            writer.end();
+        }
+
+        writer.end();
+    }
+
+    // The template leader carries no lengths, a parsed one carries those of its source
+    fn leader_with(&self, record_length: usize, base_address: usize) -> String {
+        let mut leader: Vec<char> = format!("{:<24.24}", self.leader)
+            .chars()
+            .map(|c| if c.is_ascii_graphic() { c } else { ' ' })
+            .collect();
+
+        let length: Vec<char> = format!("{:05}", record_length).chars().collect();
+        let base: Vec<char> = format!("{:05}", base_address).chars().collect();
+        leader[0..5].copy_from_slice(&length);
+        leader[12..17].copy_from_slice(&base);
+        // Data is always written as UTF-8, the indicator count, subfield code length and
+        // directory entry map are fixed
+        leader[9] = 'a';
+        leader[10] = '2';
+        leader[11] = '2';
+        leader[20..24].copy_from_slice(&['4', '5', '0', '0']);
+
+        leader.into_iter().collect()
+    }
+}
+
+// Reads every record of a MARC21 file. Records are decoded as UTF-8 or MARC-8, as their leader says.
+pub fn parse_marc21(bytes: &[u8]) -> Result<Vec<Record>, Error> {
+    let mut records = Vec::new();
+    let mut rest = bytes;
+
+    loop {
+        // Some files separate records with newlines
+        let start = rest.iter().position(|b| !b.is_ascii_whitespace()).unwrap_or(rest.len());
+        rest = &rest[start..];
+        if rest.is_empty() {
+            break;
+        }
+
+        let length = number(rest.get(0..5).ok_or_else(|| malformed("record is shorter than its leader"))?, "record length")?;
+        let record = rest.get(..length).ok_or_else(|| malformed("record length exceeds the data"))?;
+        records.push(Record::from_marc21(record)?);
+        rest = &rest[length..];
+    }
+
+    Ok(records)
+}
+
+// Reads a MARCXML <collection> or a single <record>, with or without a namespace prefix
+pub fn parse_marcxml(text: &str) -> Result<Vec<Record>, Error> {
+    let mut reader = quick_xml::Reader::from_str(text);
+    let mut records = Vec::new();
+    let mut record: Option<Record> = None;
+    let mut field: Option<DataField> = None;
+    // The element whose text is being collected and its tag or code attribute
+    let mut open: Option<(Vec<u8>, String)> = None;
+    let mut text = String::new();
+
+    loop {
+        match reader.read_event().map_err(|err| Error::Xml(err.to_string()))? {
+            Event::Start(element) => {
+                let name = element.local_name().as_ref().to_vec();
+                match name.as_slice() {
+                    b"record" => {
+                        record = Some(Record {
+                            leader: String::new(),
+                            ..Record::default()
+                        })
+                    }
+                    b"datafield" => field = Some(data_field(&element)?),
+                    b"leader" | b"controlfield" => open = Some((name, attribute(&element, b"tag")?.unwrap_or_default())),
+                    b"subfield" => open = Some((name, attribute(&element, b"code")?.unwrap_or_default())),
+                    _ => {}
+                }
+                text.clear();
+            }
+            Event::Empty(element) => match element.local_name().as_ref() {
+                b"datafield" => {
+                    if let Some(record) = record.as_mut() {
+                        record.data_fields.push(data_field(&element)?);
+                    }
+                }
+                b"controlfield" => {
+                    if let Some(record) = record.as_mut() {
+                        let tag = attribute(&element, b"tag")?.unwrap_or_default();
+                        record.control_fields.push(ControlField { tag, value: String::new() });
+                    }
+                }
+                _ => {}
+            },
+            Event::Text(content) => text.push_str(&content.unescape().map_err(|err| Error::Xml(err.to_string()))?),
+            Event::CData(content) => text.push_str(&String::from_utf8_lossy(&content)),
+            Event::End(element) => match element.local_name().as_ref() {
+                b"record" => records.extend(record.take()),
+                b"datafield" => {
+                    if let (Some(record), Some(field)) = (record.as_mut(), field.take()) {
+                        record.data_fields.push(field);
+                    }
+                }
+                _ => {
+                    if let (Some(record), Some((name, attribute))) = (record.as_mut(), open.take()) {
+                        match name.as_slice() {
+                            b"leader" => record.leader = text.clone(),
+                            b"controlfield" => record.control_fields.push(ControlField { tag: attribute, value: text.clone() }),
+                            _ => {
+                                if let Some(field) = field.as_mut() {
+                                    let code = attribute.chars().next().unwrap_or(' ');
+                                    field.subfields.push(Subfield { code, value: text.clone() });
+                                }
+                            }
+                        }
+                    }
+                }
+            },
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    Ok(records)
+}
+
+fn data_field(element: &BytesStart) -> Result<DataField, Error> {
+    let indicator = |value: Option<String>| value.and_then(|value| value.chars().next()).unwrap_or(' ');
+
+    Ok(DataField {
+        tag: attribute(element, b"tag")?.unwrap_or_default(),
+        ind1: indicator(attribute(element, b"ind1")?),
+        ind2: indicator(attribute(element, b"ind2")?),
+        subfields: Vec::new(),
+    })
+}
+
+fn attribute(element: &BytesStart, name: &[u8]) -> Result<Option<String>, Error> {
+    for attribute in element.attributes() {
+        let attribute = attribute.map_err(|err| Error::Xml(err.to_string()))?;
+        if attribute.key.local_name().as_ref() == name {
+            let value = attribute.unescape_value().map_err(|err| Error::Xml(err.to_string()))?;
+            return Ok(Some(value.into_owned()));
+        }
+    }
+
+    Ok(None)
+}
+
+fn number(digits: &[u8], what: &str) -> Result<usize, Error> {
+    std::str::from_utf8(digits)
+        .ok()
+        .and_then(|digits| digits.trim().parse().ok())
+        .ok_or_else(|| malformed(&format!("{} {:?} is not a number", what, String::from_utf8_lossy(digits))))
+}
+
+fn malformed(message: &str) -> Error {
+    Error::Malformed(message.to_string())
+}
+
+// An entry of Edition.source_records that points at a MARC record
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SourceRecord {
+    // "marc:marc_loc_2016/BooksAll.2016.part25.utf8:103836014:1017", a byte range of a file in an
+    // Internet Archive item, or the whole file when there is no range
+    Marc {
+        item: String,
+        file: String,
+        range: Option<(u64, u64)>,
+    },
+    // "ia:ocaid", the MARC record scanned books carry in their Internet Archive item
+    Ia(String),
+}
+
+impl SourceRecord {
+    // None for sources that are not MARC, like "amazon:..." or "bwb:..."
+    pub fn parse(source: &str) -> Option<Self> {
+        if let Some(ocaid) = source.strip_prefix("ia:") {
+            return Some(SourceRecord::Ia(ocaid.to_string()));
+        }
+
+        let path = source.strip_prefix("marc:")?;
+        let (item, file) = path.split_once('/')?;
+
+        let mut parts = file.rsplitn(3, ':');
+        let range = match (parts.next(), parts.next(), parts.next()) {
+            (Some(length), Some(offset), Some(file)) => match (offset.parse(), length.parse()) {
+                (Ok(offset), Ok(length)) => Some((file, Some((offset, length)))),
+                _ => None,
+            },
+            _ => None,
+        };
+
+        let (file, range) = range.unwrap_or((file, None));
+        Some(SourceRecord::Marc {
+            item: item.to_string(),
+            file: file.to_string(),
+            range,
+        })
+    }
+}
+
+impl Edition {
+    pub fn marc_source_records(&self) -> Vec<SourceRecord> {
+        self.source_records.iter().flatten().filter_map(|source| SourceRecord::parse(source)).collect()
+    }
+}
+
+// A MARC record as fetched, before parsing
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RawMarc {
+    Marc21(Vec<u8>),
+    MarcXml(String),
+}
+
+impl RawMarc {
+    pub fn parse(&self) -> Result<Record, Error> {
+        match self {
+            RawMarc::Marc21(bytes) => parse_marc21(bytes)?.into_iter().next().ok_or_else(|| malformed("no record")),
+            RawMarc::MarcXml(text) => Record::from_marcxml(text),
+        }
+    }
+}
+
+// Writes a <collection> of MARCXML records
+pub fn write_marcxml<W: Write>(writer: &mut W, records: &[Record]) -> io::Result<()> {
+    let mut xml = xml::Writer::new();
+    xml.start(
+        "collection",
+        &[
+            ("xmlns", MARCXML_NAMESPACE),
+            ("xmlns:xsi", "http://www.w3.org/2001/XMLSchema-instance"),
+            ("xsi:schemaLocation", MARCXML_SCHEMA_LOCATION),
+        ],
+    );
+    for record in records {
+        record.write_xml(&mut xml, false);
+    }
+
+    writer.write_all(xml.finish().as_bytes())
+}
+
+impl Edition {
+    // Without the work and author records there are no 100/700 name fields
+    pub fn to_marc(&self) -> Record {
+        marc_record(self, &[], &[])
+    }
+}
+
+impl Entity {
+    pub fn to_marc(&self) -> Record {
+        marc_record(&self.edition, &self.authors, self.work.subjects.as_deref().unwrap_or_default())
+    }
+
+    pub fn to_marc21(&self) -> Result<Vec<u8>, Error> {
+        self.to_marc().to_marc21()
+    }
+
+    pub fn to_marcxml(&self) -> String {
+        self.to_marc().to_marcxml()
+    }
+}
+
+fn marc_record(edition: &Edition, authors: &[Author], work_subjects: &[String]) -> Record {
+    let mut record = Record::default();
+    let date = edition.parsed_publish_date();
+
+    record.control("001", &crate::client::process_olid_key(&edition.key));
+    if let Some(modified) = edition.last_modified.value {
+        record.control("005", &modified.format("%Y%m%d%H%M%S.0").to_string());
+    }
+    record.control("008", &fixed_length_data(edition, date));
+
+    for lccn in edition.lccn.iter().flatten() {
+        record.data(DataField::new("010", ' ', ' ').subfield('a', lccn));
+    }
+    for isbn in edition.isbn13.iter().chain(edition.isbn10.iter()).flatten() {
+        record.data(DataField::new("020", ' ', ' ').subfield('a', isbn));
+    }
+    for oclc in edition.oclc_numbers.iter().flatten() {
+        record.data(DataField::new("035", ' ', ' ').subfield('a', &format!("(OCoLC){}", oclc)));
+    }
+    for classification in edition.lc_classifications.iter().flatten() {
+        // "PR4034 .P7 2003" is class number PR4034 and item number .P7 2003
+        let mut field = DataField::new("050", ' ', '4');
+        field = match classification.split_once(' ') {
+            Some((class, item)) => field.subfield('a', class).subfield('b', item.trim()),
+            None => field.subfield('a', classification),
+        };
+        record.data(field);
+    }
+    for dewey in edition.dewey_decimal_class.iter().flatten() {
+        record.data(DataField::new("082", '0', '4').subfield('a', dewey));
+    }
+
+    if let Some((first, others)) = authors.split_first() {
+        record.data(name_field(first, true));
+        for author in others {
+            record.data(name_field(author, false));
+        }
+    }
+
+    let mut title = DataField::new("245", if authors.is_empty() { '0' } else { '1' }, nonfiling_characters(&edition.title));
+    title = title.subfield('a', &edition.title);
+    if let Some(subtitle) = edition.subtitle.as_ref().filter(|subtitle| !subtitle.is_empty()) {
+        title = title.subfield('b', subtitle);
+    }
+    if let Some(by_statement) = &edition.by_statement {
+        title = title.subfield('c', by_statement);
+    }
+    record.data(title);
+
+    if let Some(edition_name) = edition.edition_statement() {
+        record.data(DataField::new("250", ' ', ' ').subfield('a', edition_name));
+    }
+
+    let mut publication = DataField::new("264", ' ', '1');
+    for place in edition.publish_places.iter().flatten() {
+        publication = publication.subfield('a', place);
+    }
+    for publisher in edition.publishers.iter().flatten() {
+        publication = publication.subfield('b', publisher);
+    }
+    if let Some(publish_date) = &edition.publish_date {
+        publication = publication.subfield('c', publish_date);
+    }
+    record.data(publication);
+
+    let extent = match (&edition.pagination, edition.number_of_pages) {
+        (Some(pagination), _) => Some(pagination.clone()),
+        (None, Some(pages)) => Some(format!("{} pages", pages)),
+        (None, None) => None,
+    };
+    let mut description = DataField::new("300", ' ', ' ');
+    if let Some(extent) = &extent {
+        description = description.subfield('a', extent);
+    }
+    if let Some(dimensions) = &edition.physical_dimensions {
+        description = description.subfield('c', dimensions);
+    }
+    record.data(description);
+
+    for series in edition.series.iter().flatten() {
+        record.data(DataField::new("490", '0', ' ').subfield('a', series));
+    }
+
+    let contents: Vec<&str> = edition
+        .table_of_contents
+        .iter()
+        .flatten()
+        .map(|entry| entry.title.trim())
+        .filter(|title| !title.is_empty())
+        .collect();
+    if !contents.is_empty() {
+        record.data(DataField::new("505", '0', ' ').subfield('a', &contents.join(" -- ")));
+    }
+
+    let mut subjects: Vec<&String> = Vec::new();
+    for subject in edition.subjects.iter().flatten().chain(work_subjects.iter()) {
+        if !subjects.contains(&subject) {
+            subjects.push(subject);
+        }
+    }
+    for subject in subjects {
+        // Second indicator 4, source not specified
+        record.data(DataField::new("650", ' ', '4').subfield('a', subject));
+    }
+
+    // Added entries were pushed with the main entry, fields are kept in tag order
+    record.data_fields.sort_by(|a, b| a.tag.cmp(&b.tag));
+
+    record
+}
+
+// 100/700 for persons ("Austen, Jane", dates in $d), 110/710 for organizations
+fn name_field(author: &Author, main_entry: bool) -> DataField {
+    if author.is_organization() {
+        let tag = if main_entry { "110" } else { "710" };
+        return DataField::new(tag, '2', ' ').subfield('a', author.name.trim()).subfield('e', "author");
+    }
+
+    let name = author.personal_name();
+    let heading = match &name.given {
+        Some(given) => format!("{}, {}", name.family, given),
+        None => name.family.clone(),
+    };
+    // First indicator 1 is a surname, 0 a forename only name like "Homer"
+    let ind1 = if name.given.is_some() { '1' } else { '0' };
+    let tag = if main_entry { "100" } else { "700" };
+
+    let mut field = DataField::new(tag, ind1, ' ').subfield('a', &heading);
+    if let Some(suffix) = &name.suffix {
+        field = field.subfield('c', suffix);
+    }
+
+    if let Some(dates) = author.life_dates() {
+        field = field.subfield('d', &dates);
+    }
+
+    field.subfield('e', "author")
+}
+
+// 008 for books, 40 positions
+fn fixed_length_data(edition: &Edition, date: Option<PartialDate>) -> String {
+    let entered = match edition.created.value {
+        Some(created) => created.format("%y%m%d").to_string(),
+        None => String::from("      "),
+    };
+
+    // s: single known date, q: questionable date, n: unknown
+    let (date_type, year) = match date {
+        Some(date) if date.circa || date.uncertain => ('q', format!("{:04}", date.year)),
+        Some(date) => ('s', format!("{:04}", date.year)),
+        None => ('n', String::from("uuuu")),
+    };
+
+    let place = edition.publish_country.as_deref().map(str::trim).filter(|place| !place.is_empty()).unwrap_or("xx");
+
+    let language = edition
+        .languages
+        .iter()
+        .flatten()
+        .next()
+        .map(|language| language.code().to_string())
+        .unwrap_or_else(|| String::from("und"));
+
+    // Positions 18-34 are not coded, "|" means no attempt to code
+    format!(
+        "{}{}{}    {:<3.3}{}{:<3.3} d",
+        entered,
+        date_type,
+        year,
+        place,
+        "|".repeat(17),
+        language
+    )
+}
+
+// Second indicator of 245: how many leading characters to skip when filing, "The " is 4
+fn nonfiling_characters(title: &str) -> char {
+    let lower = title.to_lowercase();
+    let article = ["the ", "an ", "a "].iter().find(|article| lower.starts_with(*article));
+
+    match article {
+        Some(article) => std::char::from_digit(article.len() as u32, 10).unwrap_or('0'),
+        None => '0',
+    }
+}
+
+fn encode_control(field: &ControlField) -> Vec<u8> {
+    let mut bytes = clean(&field.value).into_bytes();
+    bytes.push(FIELD_TERMINATOR);
+
+    bytes
+}
+
+fn encode_data(field: &DataField) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    let mut indicators = [0; 4];
+    bytes.extend_from_slice(field.ind1.encode_utf8(&mut indicators).as_bytes());
+    bytes.extend_from_slice(field.ind2.encode_utf8(&mut indicators).as_bytes());
+
+    for subfield in field.subfields.iter() {
+        bytes.push(SUBFIELD_DELIMITER);
+        bytes.extend_from_slice(subfield.code.encode_utf8(&mut indicators).as_bytes());
+        bytes.extend_from_slice(clean(&subfield.value).as_bytes());
+    }
+    bytes.push(FIELD_TERMINATOR);
+
+    bytes
+}
+
+// The delimiter and terminator bytes cannot appear in field data
+fn clean(value: &str) -> String {
+    value.chars().filter(|c| !matches!(*c as u32, 0x1D..=0x1F)).collect()
+}
+
+// Strips the ISBD punctuation cataloguers end subfields with, "Pride and prejudice /" is
+// "Pride and prejudice". A final period stays when it ends an abbreviation like "p." or "ed.".
+fn trim_punctuation(value: &str) -> &str {
+    let value = value.trim().trim_end_matches([' ', '/', ':', ';', ',', '=']).trim_end();
+
+    match value.strip_suffix('.') {
+        Some(stripped) if !ends_with_abbreviation(stripped) => stripped,
+        _ => value,
+    }
+}
+
+fn ends_with_abbreviation(value: &str) -> bool {
+    let last = value.rsplit([' ', '.']).next().unwrap_or_default();
+
+    let short = !last.is_empty() && last.len() <= 2 && !last.chars().all(|c| c.is_ascii_digit());
+
+    short || ["ill", "illus", "inc", "ltd", "etc", "col", "pbk"].contains(&last.to_lowercase().as_str())
+}
+
+fn non_empty<T>(values: Vec<T>) -> Option<Vec<T>> {
+    if values.is_empty() {
+        None
+    } else {
+        Some(values)
+    }
+}
@@ -0,0 +1,118 @@
+// MARC-8, the character set of MARC21 records with a blank leader position 9: ASCII plus the
+// ANSEL extended Latin set. Diacritics are written before the letter they go on, Unicode puts
+// combining marks after it, so they are held back until the letter has been written. Other
+// character sets are selected with escape sequences, which are not supported.
+
+use crate::marc::Error;
+
+const ESCAPE: u8 = 0x1B;
+
+// ANSEL 0xA1..=0xC8, spacing characters
+const SPACING: [(u8, char); 36] = [
+    (0xA1, 'Ł'),
+    (0xA2, 'Ø'),
+    (0xA3, 'Đ'),
+    (0xA4, 'Þ'),
+    (0xA5, 'Æ'),
+    (0xA6, 'Œ'),
+    (0xA7, 'ʹ'),
+    (0xA8, '·'),
+    (0xA9, '♭'),
+    (0xAA, '®'),
+    (0xAB, '±'),
+    (0xAC, 'Ơ'),
+    (0xAD, 'Ư'),
+    (0xAE, 'ʼ'),
+    (0xB0, 'ʻ'),
+    (0xB1, 'ł'),
+    (0xB2, 'ø'),
+    (0xB3, 'đ'),
+    (0xB4, 'þ'),
+    (0xB5, 'æ'),
+    (0xB6, 'œ'),
+    (0xB7, 'ʺ'),
+    (0xB8, 'ı'),
+    (0xB9, '£'),
+    (0xBA, 'ð'),
+    (0xBC, 'ơ'),
+    (0xBD, 'ư'),
+    (0xC0, '°'),
+    (0xC1, 'ℓ'),
+    (0xC2, '℗'),
+    (0xC3, '©'),
+    (0xC4, '♯'),
+    (0xC5, '¿'),
+    (0xC6, '¡'),
+    (0xC7, 'ß'),
+    (0xC8, '€'),
+];
+
+// ANSEL 0xE0..=0xFE, combining diacritics
+const COMBINING: [(u8, char); 29] = [
+    (0xE0, '\u{0309}'),
+    (0xE1, '\u{0300}'),
+    (0xE2, '\u{0301}'),
+    (0xE3, '\u{0302}'),
+    (0xE4, '\u{0303}'),
+    (0xE5, '\u{0304}'),
+    (0xE6, '\u{0306}'),
+    (0xE7, '\u{0307}'),
+    (0xE8, '\u{0308}'),
+    (0xE9, '\u{030C}'),
+    (0xEA, '\u{030A}'),
+    (0xEB, '\u{FE20}'),
+    (0xEC, '\u{FE21}'),
+    (0xED, '\u{0315}'),
+    (0xEE, '\u{030B}'),
+    (0xEF, '\u{0310}'),
+    (0xF0, '\u{0327}'),
+    (0xF1, '\u{0328}'),
+    (0xF2, '\u{0323}'),
+    (0xF3, '\u{0324}'),
+    (0xF4, '\u{0325}'),
+    (0xF5, '\u{0333}'),
+    (0xF6, '\u{0332}'),
+    (0xF7, '\u{0326}'),
+    (0xF8, '\u{031C}'),
+    (0xF9, '\u{032E}'),
+    (0xFA, '\u{FE22}'),
+    (0xFB, '\u{FE23}'),
+    (0xFE, '\u{0313}'),
+];
+
+pub(crate) fn decode(bytes: &[u8]) -> Result<String, Error> {
+    let mut text = String::with_capacity(bytes.len());
+    let mut marks = String::new();
+
+    for byte in bytes.iter().copied() {
+        if byte == ESCAPE {
+            return Err(Error::UnsupportedEncoding(String::from("MARC-8 with escape sequences to other character sets")));
+        }
+
+        if let Some((_, mark)) = COMBINING.iter().find(|(code, _)| *code == byte) {
+            marks.push(*mark);
+            continue;
+        }
+
+        let c = match byte {
+            0x00..=0x7F => byte as char,
+            _ => match SPACING.iter().find(|(code, _)| *code == byte) {
+                Some((_, c)) => *c,
+                None => return Err(Error::UnsupportedEncoding(format!("byte {:#04X} is not a MARC-8 character", byte))),
+            },
+        };
+
+        // Delimiters and terminators do not take diacritics
+        if c.is_control() {
+            text.push_str(&marks);
+            text.push(c);
+        } else {
+            text.push(c);
+            text.push_str(&marks);
+        }
+        marks.clear();
+    }
+    text.push_str(&marks);
+
+    Ok(text)
+}
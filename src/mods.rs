@@ -0,0 +1,196 @@
+use crate::entity::Entity;
+use crate::xml;
+
+const MODS_NAMESPACE: &str = "http://www.loc.gov/mods/v3";
+const MODS_SCHEMA_LOCATION: &str = "http://www.loc.gov/mods/v3 http://www.loc.gov/standards/mods/v3/mods-3-7.xsd";
+
+// Leading articles MODS moves into nonSort
+const NON_SORT: [&str; 3] = ["The ", "A ", "An "];
+
+impl Entity {
+    // A MODS 3.7 record
+    pub fn to_mods_xml(&self) -> String {
+        let edition = &self.edition;
+        let mut writer = xml::Writer::new();
+
+        writer.start(
+            "mods",
+            &[
+                ("xmlns", MODS_NAMESPACE),
+                ("xmlns:xsi", "http://www.w3.org/2001/XMLSchema-instance"),
+                ("version", "3.7"),
+                ("xsi:schemaLocation", MODS_SCHEMA_LOCATION),
+            ],
+        );
+
+        let (title, subtitle) = self.title_parts();
+        writer.start("titleInfo", &[]);
+        match NON_SORT.iter().find(|article| title.starts_with(*article)) {
+            Some(article) => {
+                writer.element("nonSort", &[], article);
+                writer.element("title", &[], &title[article.len()..]);
+            }
+            None => writer.element("title", &[], title),
+        }
+        if let Some(subtitle) = subtitle {
+            writer.element("subTitle", &[], subtitle);
+        }
+        writer.end();
+
+        for (index, author) in self.authors.iter().enumerate() {
+            let mut attributes = vec![("type", if author.is_organization() { "corporate" } else { "personal" })];
+            if index == 0 {
+                attributes.push(("usage", "primary"));
+            }
+            writer.start("name", &attributes);
+
+            if author.is_organization() {
+                writer.element("namePart", &[], author.name.trim());
+            } else {
+                let name = author.personal_name();
+                writer.element("namePart", &[("type", "family")], &name.family);
+                if let Some(given) = &name.given {
+                    writer.element("namePart", &[("type", "given")], given);
+                }
+                if let Some(suffix) = &name.suffix {
+                    writer.element("namePart", &[("type", "termsOfAddress")], suffix);
+                }
+                if let Some(dates) = author.life_dates() {
+                    writer.element("namePart", &[("type", "date")], &dates);
+                }
+            }
+
+            writer.start("role", &[]);
+            writer.element("roleTerm", &[("type", "text"), ("authority", "marcrelator")], "author");
+            writer.element("roleTerm", &[("type", "code"), ("authority", "marcrelator")], "aut");
+            writer.end();
+            writer.end();
+        }
+
+        writer.element("typeOfResource", &[], "text");
+        writer.element("genre", &[("authority", "marcgt")], "book");
+
+        writer.start("originInfo", &[("eventType", "publication")]);
+        for place in edition.publish_places.iter().flatten() {
+            writer.start("place", &[]);
+            writer.element("placeTerm", &[("type", "text")], place);
+            writer.end();
+        }
+        if let Some(country) = &edition.publish_country {
+            writer.start("place", &[]);
+            writer.element("placeTerm", &[("type", "code"), ("authority", "marccountry")], country.trim());
+            writer.end();
+        }
+        for publisher in edition.publishers.iter().flatten() {
+            writer.element("publisher", &[], publisher);
+        }
+        if let Some(publish_date) = &edition.publish_date {
+            writer.element("dateIssued", &[], publish_date);
+        }
+        if let Some(date) = edition.parsed_publish_date() {
+            let mut attributes = vec![("encoding", "w3cdtf"), ("keyDate", "yes")];
+            if date.circa {
+                attributes.push(("qualifier", "approximate"));
+            } else if date.uncertain {
+                attributes.push(("qualifier", "questionable"));
+            }
+            writer.element("dateIssued", &attributes, &date.to_iso8601());
+        }
+        if let Some(edition_name) = edition.edition_statement() {
+            writer.element("edition", &[], edition_name);
+        }
+        writer.end();
+
+        for language in edition.languages.iter().flatten() {
+            writer.start("language", &[]);
+            writer.element("languageTerm", &[("type", "code"), ("authority", "iso639-2b")], language.iso639_2b().unwrap_or_else(|| language.code()));
+            if let Some(name) = language.name() {
+                writer.element("languageTerm", &[("type", "text")], name);
+            }
+            writer.end();
+        }
+
+        let extent = edition
+            .pagination
+            .clone()
+            .or_else(|| edition.number_of_pages.map(|pages| format!("{} pages", pages)));
+        if edition.physical_format.is_some() || extent.is_some() || edition.physical_dimensions.is_some() {
+            writer.start("physicalDescription", &[]);
+            if let Some(format) = &edition.physical_format {
+                writer.element("form", &[], format);
+            }
+            if let Some(extent) = &extent {
+                writer.element("extent", &[], extent);
+            }
+            if let Some(dimensions) = &edition.physical_dimensions {
+                writer.element("extent", &[("unit", "dimensions")], dimensions);
+            }
+            writer.end();
+        }
+
+        if let Some(description) = self.description() {
+            writer.element("abstract", &[], description);
+        }
+
+        let contents: Vec<&str> = edition
+            .table_of_contents
+            .iter()
+            .flatten()
+            .map(|entry| entry.title.trim())
+            .filter(|title| !title.is_empty())
+            .collect();
+        if !contents.is_empty() {
+            writer.element("tableOfContents", &[], &contents.join(" -- "));
+        }
+
+        for subject in self.subjects() {
+            writer.start("subject", &[]);
+            writer.element("topic", &[], subject);
+            writer.end();
+        }
+
+        for classification in edition.lc_classifications.iter().flatten() {
+            writer.element("classification", &[("authority", "lcc")], classification);
+        }
+        for classification in edition.dewey_decimal_class.iter().flatten() {
+            writer.element("classification", &[("authority", "ddc")], classification);
+        }
+
+        for series in edition.series.iter().flatten() {
+            writer.start("relatedItem", &[("type", "series")]);
+            writer.start("titleInfo", &[]);
+            writer.element("title", &[], series);
+            writer.end();
+            writer.end();
+        }
+
+        for isbn in edition.isbn13.iter().chain(edition.isbn10.iter()).flatten() {
+            writer.element("identifier", &[("type", "isbn")], isbn);
+        }
+        for lccn in edition.lccn.iter().flatten() {
+            writer.element("identifier", &[("type", "lccn")], lccn);
+        }
+        for oclc in edition.oclc_numbers.iter().flatten() {
+            writer.element("identifier", &[("type", "oclc")], oclc);
+        }
+        let url = format!("https://openlibrary.org/books/{}", self.olid);
+        writer.element("identifier", &[("type", "uri")], &url);
+
+        writer.start("location", &[]);
+        writer.element("url", &[("usage", "primary display")], &url);
+        writer.end();
+
+        writer.start("recordInfo", &[]);
+        writer.element("recordContentSource", &[], "Open Library");
+        if let Some(created) = edition.created.value {
+            writer.element("recordCreationDate", &[("encoding", "w3cdtf")], &created.format("%Y-%m-%d").to_string());
+        }
+        if let Some(modified) = edition.last_modified.value {
+            writer.element("recordChangeDate", &[("encoding", "w3cdtf")], &modified.format("%Y-%m-%d").to_string());
+        }
+        writer.element("recordIdentifier", &[("source", "Open Library")], &self.olid);
+        writer.end();
+
+        writer.finish()
+    }
+}
@@ -0,0 +1,130 @@
+use crate::author::Author;
+use crate::date::PartialDate;
+
+// Lowercase particles that belong to the family name, "Ludwig van Beethoven" is "van Beethoven, Ludwig"
+const PARTICLES: [&str; 16] = [
+    "van", "von", "de", "der", "den", "du", "da", "di", "del", "della", "des", "la", "le", "ter", "ten", "zu",
+];
+
+const SUFFIXES: [&str; 7] = ["jr.", "jr", "sr.", "sr", "ii", "iii", "iv"];
+
+// Open Library author names are in natural order ("Jane Austen"), citation formats want them split
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PersonalName {
+    pub family: String,
+    pub given: Option<String>,
+    pub suffix: Option<String>,
+}
+
+impl PersonalName {
+    pub fn parse(name: &str) -> Self {
+        let name = name.trim();
+
+        // Already inverted, "Austen, Jane" or "King, Martin Luther, Jr."
+        if let Some((family, rest)) = name.split_once(',') {
+            let mut parts = rest.splitn(2, ',').map(str::trim).filter(|part| !part.is_empty());
+            let given = parts.next().map(String::from);
+            let suffix = parts.next().map(String::from);
+            return Self { family: family.trim().to_string(), given, suffix };
+        }
+
+        let mut words: Vec<&str> = name.split_whitespace().collect();
+        let suffix = match words.last() {
+            Some(last) if words.len() > 2 && SUFFIXES.contains(&last.to_lowercase().as_str()) => words.pop().map(String::from),
+            _ => None,
+        };
+
+        if words.len() < 2 {
+            return Self { family: words.join(" "), given: None, suffix };
+        }
+
+        let last = words.len() - 1;
+        let family_start = (1..last).find(|&i| PARTICLES.contains(&words[i])).unwrap_or(last);
+
+        Self {
+            family: words[family_start..].join(" "),
+            given: Some(words[..family_start].join(" ")),
+            suffix,
+        }
+    }
+
+    // The family name without particles, "Beethoven" for "van Beethoven"
+    pub fn family_core(&self) -> &str {
+        self.family
+            .split_whitespace()
+            .rfind(|word| !PARTICLES.contains(word))
+            .unwrap_or(&self.family)
+    }
+}
+
+impl Author {
+    pub(crate) fn personal_name(&self) -> PersonalName {
+        PersonalName::parse(&self.name)
+    }
+
+    // "1775-1817", "1947-" for the living
+    pub(crate) fn life_dates(&self) -> Option<String> {
+        let birth = self.birth_date.as_deref().and_then(PartialDate::parse).map(|date| date.year.to_string());
+        let death = self.death_date.as_deref().and_then(PartialDate::parse).map(|date| date.year.to_string());
+
+        if birth.is_none() && death.is_none() {
+            return None;
+        }
+
+        Some(format!("{}-{}", birth.unwrap_or_default(), death.unwrap_or_default()))
+    }
+
+    // entity_type is "person" or "org", most records leave it out
+    pub(crate) fn is_organization(&self) -> bool {
+        self.entity_type.as_deref() == Some("org")
+    }
+}
+
+// Plain ASCII for identifiers such as citation keys, accents are dropped and other letters skipped
+pub(crate) fn ascii_fold(text: &str) -> String {
+    let mut folded = String::new();
+    for c in text.chars() {
+        let replacement = match c {
+            'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' => "a",
+            'ç' | 'č' | 'ć' => "c",
+            'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ě' => "e",
+            'ì' | 'í' | 'î' | 'ï' | 'ī' => "i",
+            'ñ' | 'ń' | 'ň' => "n",
+            'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' => "o",
+            'ù' | 'ú' | 'û' | 'ü' | 'ū' | 'ů' => "u",
+            'ý' | 'ÿ' => "y",
+            'š' | 'ś' => "s",
+            'ž' | 'ź' | 'ż' => "z",
+            'ł' => "l",
+            'ř' => "r",
+            'ß' => "ss",
+            'æ' => "ae",
+            'œ' => "oe",
+            c if c.is_ascii_alphanumeric() => {
+                folded.push(c);
+                continue;
+            }
+            _ => continue,
+        };
+        folded.push_str(replacement);
+    }
+
+    folded
+}
+
+// "J." for Jane, "J. R. R." for J. R. R., "J.-P." for Jean-Paul
+pub(crate) fn initials(given: &str) -> String {
+    let initials: Vec<String> = given
+        .split_whitespace()
+        .map(|word| {
+            let parts: Vec<String> = word
+                .split('-')
+                .filter_map(|part| part.chars().next())
+                .map(|c| format!("{}.", c))
+                .collect();
+            parts.join("-")
+        })
+        .collect();
+
+    initials.join(" ")
+}
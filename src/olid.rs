@@ -0,0 +1,70 @@
+use std::error;
+use std::fmt;
+use std::str::FromStr;
+
+// An Open Library identifier like OL7353617M. The suffix tells the record type apart.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Olid {
+    Edition(String),
+    Work(String),
+    Author(String),
+}
+
+impl Olid {
+    pub fn id(&self) -> &str {
+        match self {
+            Olid::Edition(id) | Olid::Work(id) | Olid::Author(id) => id,
+        }
+    }
+
+    pub fn key(&self) -> String {
+        match self {
+            Olid::Edition(id) => format!("/books/{}", id),
+            Olid::Work(id) => format!("/works/{}", id),
+            Olid::Author(id) => format!("/authors/{}", id),
+        }
+    }
+}
+
+impl fmt::Display for Olid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.id())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidOlid(pub String);
+
+impl fmt::Display for InvalidOlid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} is not an edition, work or author OLID", self.0)
+    }
+}
+
+impl error::Error for InvalidOlid {}
+
+// Accepts the bare id as well as keys like /books/OL7353617M
+impl FromStr for Olid {
+    type Err = InvalidOlid;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let id = s.trim().trim_end_matches(".json").rsplit('/').next().unwrap_or_default();
+        let invalid = || InvalidOlid(String::from(s));
+
+        // Checked first so the byte slicing below stays on char boundaries
+        if !id.is_ascii() {
+            return Err(invalid());
+        }
+        let digits = id.strip_prefix("OL").ok_or_else(invalid)?;
+        if digits.len() < 2 || !digits[..digits.len() - 1].chars().all(|c| c.is_ascii_digit()) {
+            return Err(invalid());
+        }
+
+        match id.chars().last() {
+            Some('M') => Ok(Olid::Edition(String::from(id))),
+            Some('W') => Ok(Olid::Work(String::from(id))),
+            Some('A') => Ok(Olid::Author(String::from(id))),
+            _ => Err(invalid()),
+        }
+    }
+}
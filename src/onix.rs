@@ -0,0 +1,345 @@
+use std::io::{self, Write};
+
+use chrono::Utc;
+
+use crate::covers::CoverSize;
+use crate::entity::Entity;
+use crate::isbn;
+use crate::physical::LengthUnit;
+use crate::xml;
+
+const ONIX_NAMESPACE: &str = "http://ns.editeur.org/onix/3.0/reference";
+
+// Leading articles ONIX moves into TitlePrefix
+const TITLE_PREFIXES: [&str; 3] = ["The ", "A ", "An "];
+
+// Contributions are free text, "Illustrated by Quentin Blake" or "Quentin Blake (Illustrator)".
+// Codes are from ONIX code list 17, contributor role.
+const ROLES: [(&str, &str); 8] = [
+    ("illustrat", "A12"),
+    ("translat", "B06"),
+    ("edit", "B01"),
+    ("introduc", "A24"),
+    ("foreword", "A23"),
+    ("afterword", "A19"),
+    ("photograph", "A13"),
+    ("narrat", "E03"),
+];
+
+impl Entity {
+    // An ONIXMessage holding this entity's Product, sent by `sender`
+    pub fn to_onix(&self, sender: &str) -> String {
+        let mut writer = xml::Writer::new();
+        message(&mut writer, sender, std::slice::from_ref(self));
+
+        writer.finish()
+    }
+}
+
+// Writes one ONIXMessage with a Product per entity, the way retail partners expect feeds
+pub fn write_onix<W: Write>(writer: &mut W, sender: &str, entities: &[Entity]) -> io::Result<()> {
+    let mut xml_writer = xml::Writer::new();
+    message(&mut xml_writer, sender, entities);
+
+    writer.write_all(xml_writer.finish().as_bytes())
+}
+
+fn message(writer: &mut xml::Writer, sender: &str, entities: &[Entity]) {
+    writer.start("ONIXMessage", &[("xmlns", ONIX_NAMESPACE), ("release", "3.0")]);
+
+    writer.start("Header", &[]);
+    writer.start("Sender", &[]);
+    writer.element("SenderName", &[], sender);
+    writer.end();
+    writer.element("SentDateTime", &[], &Utc::now().format("%Y%m%dT%H%M%SZ").to_string());
+    writer.end();
+
+    for entity in entities {
+        product(writer, entity);
+    }
+
+    writer.end();
+}
+
+fn product(writer: &mut xml::Writer, entity: &Entity) {
+    let edition = &entity.edition;
+
+    writer.start("Product", &[]);
+    writer.element("RecordReference", &[], &format!("org.openlibrary.{}", entity.olid));
+    writer.element("NotificationType", &[], "03");
+
+    let isbn13 = edition
+        .isbn13
+        .iter()
+        .flatten()
+        .map(|isbn| isbn.replace(['-', ' '], ""))
+        .next()
+        .or_else(|| edition.isbn10.iter().flatten().find_map(|isbn| isbn::to_isbn13(isbn)));
+    if let Some(isbn13) = isbn13 {
+        writer.start("ProductIdentifier", &[]);
+        writer.element("ProductIDType", &[], "15");
+        writer.element("IDValue", &[], &isbn13);
+        writer.end();
+    }
+    writer.start("ProductIdentifier", &[]);
+    writer.element("ProductIDType", &[], "01");
+    writer.element("IDTypeName", &[], "Open Library");
+    writer.element("IDValue", &[], &entity.olid);
+    writer.end();
+
+    // Block 1, product description
+    writer.start("DescriptiveDetail", &[]);
+    writer.element("ProductComposition", &[], "00");
+    writer.element("ProductForm", &[], product_form(edition.physical_format.as_deref().unwrap_or_default()));
+
+    if let Some(dimensions) = edition.dimensions() {
+        let dimensions = dimensions.convert(LengthUnit::Millimeters);
+        let measures = [("01", Some(dimensions.height)), ("02", dimensions.width), ("03", dimensions.depth)];
+        for (measure_type, value) in measures.iter() {
+            if let Some(value) = value {
+                measure(writer, measure_type, &format!("{:.0}", value), "mm");
+            }
+        }
+    }
+    if let Some(grams) = edition.weight_grams() {
+        measure(writer, "08", &format!("{:.0}", grams), "gr");
+    }
+
+    for series in edition.series.iter().flatten() {
+        writer.start("Collection", &[]);
+        writer.element("CollectionType", &[], "10");
+        writer.start("TitleDetail", &[]);
+        writer.element("TitleType", &[], "01");
+        writer.start("TitleElement", &[]);
+        writer.element("TitleElementLevel", &[], "02");
+        writer.element("TitleText", &[], series.trim());
+        writer.end();
+        writer.end();
+        writer.end();
+    }
+
+    let (title, subtitle) = entity.title_parts();
+    writer.start("TitleDetail", &[]);
+    writer.element("TitleType", &[], "01");
+    writer.start("TitleElement", &[]);
+    writer.element("TitleElementLevel", &[], "01");
+    match TITLE_PREFIXES.iter().find(|prefix| title.starts_with(*prefix)) {
+        Some(prefix) => {
+            writer.element("TitlePrefix", &[], prefix.trim());
+            writer.element("TitleWithoutPrefix", &[], &title[prefix.len()..]);
+        }
+        None => writer.element("TitleText", &[], title),
+    }
+    if let Some(subtitle) = subtitle {
+        writer.element("Subtitle", &[], subtitle);
+    }
+    writer.end();
+    writer.end();
+
+    let mut sequence = 0;
+    for author in entity.authors.iter().filter(|author| !author.name.trim().is_empty()) {
+        sequence += 1;
+        writer.start("Contributor", &[]);
+        writer.element("SequenceNumber", &[], &sequence.to_string());
+        writer.element("ContributorRole", &[], "A01");
+        if author.is_organization() {
+            writer.element("CorporateName", &[], author.name.trim());
+        } else {
+            let name = author.personal_name();
+            writer.element("PersonName", &[], author.name.trim());
+            if let Some(given) = &name.given {
+                writer.element("PersonNameInverted", &[], &format!("{}, {}", name.family, given));
+                writer.element("NamesBeforeKey", &[], given);
+            }
+            // "van Beethoven" is PrefixToKey "van" and KeyNames "Beethoven"
+            let key_names = name.family_core();
+            match name.family.strip_suffix(key_names).map(str::trim).filter(|prefix| !prefix.is_empty()) {
+                Some(prefix) => {
+                    writer.element("PrefixToKey", &[], prefix);
+                    writer.element("KeyNames", &[], key_names);
+                }
+                None => writer.element("KeyNames", &[], &name.family),
+            }
+            if let Some(suffix) = &name.suffix {
+                writer.element("SuffixToKey", &[], suffix);
+            }
+        }
+        writer.end();
+    }
+    for contribution in edition.contributions.iter().flatten() {
+        let (role, name) = contributor_role(contribution);
+        if name.is_empty() {
+            continue;
+        }
+
+        sequence += 1;
+        writer.start("Contributor", &[]);
+        writer.element("SequenceNumber", &[], &sequence.to_string());
+        writer.element("ContributorRole", &[], role);
+        writer.element("PersonName", &[], name);
+        writer.end();
+    }
+    if sequence == 0 {
+        writer.empty("NoContributor", &[]);
+    }
+
+    if let Some(edition_name) = edition.edition_statement() {
+        writer.element("EditionStatement", &[], edition_name);
+    }
+
+    for language in edition.languages.iter().flatten() {
+        writer.start("Language", &[]);
+        writer.element("LanguageRole", &[], "01");
+        writer.element("LanguageCode", &[], language.iso639_2b().unwrap_or_else(|| language.code()));
+        writer.end();
+    }
+
+    // Main content from the arabic numbered pages when the pagination parses
+    let pagination = edition.parsed_pagination();
+    let main_content = pagination
+        .map(|pagination| pagination.body)
+        .filter(|pages| *pages > 0)
+        .or_else(|| edition.page_count());
+    if let Some(pages) = main_content {
+        extent(writer, "00", pages);
+    }
+    if let Some(front_matter) = pagination.map(|pagination| pagination.front_matter).filter(|pages| *pages > 0) {
+        extent(writer, "03", front_matter);
+    }
+
+    for classification in edition.dewey_decimal_class.iter().flatten() {
+        subject(writer, "01", classification);
+    }
+    for classification in edition.lc_classifications.iter().flatten() {
+        subject(writer, "03", classification);
+    }
+    let keywords = entity.subjects();
+    if !keywords.is_empty() {
+        subject(writer, "20", &keywords.join("; "));
+    }
+    writer.end();
+
+    // Block 2, marketing collateral
+    let description = entity.description();
+    let cover = edition.cover_url(CoverSize::L);
+    if description.is_some() || cover.is_some() {
+        writer.start("CollateralDetail", &[]);
+        if let Some(description) = description {
+            writer.start("TextContent", &[]);
+            writer.element("TextType", &[], "03");
+            writer.element("ContentAudience", &[], "00");
+            writer.element("Text", &[("textformat", "06")], description);
+            writer.end();
+        }
+        if let Some(cover) = cover {
+            writer.start("SupportingResource", &[]);
+            writer.element("ResourceContentType", &[], "01");
+            writer.element("ContentAudience", &[], "00");
+            writer.element("ResourceMode", &[], "03");
+            writer.start("ResourceVersion", &[]);
+            writer.element("ResourceForm", &[], "02");
+            writer.element("ResourceLink", &[], &cover);
+            writer.end();
+            writer.end();
+        }
+        writer.end();
+    }
+
+    // Block 4, publisher and publishing dates
+    writer.start("PublishingDetail", &[]);
+    for publisher in edition.publishers.iter().flatten() {
+        writer.start("Publisher", &[]);
+        writer.element("PublishingRole", &[], "01");
+        writer.element("PublisherName", &[], publisher.trim());
+        writer.end();
+    }
+    for place in edition.publish_places.iter().flatten() {
+        writer.element("CityOfPublication", &[], place.trim());
+    }
+    if let Some(date) = edition.parsed_publish_date() {
+        let (format, value) = match (date.month, date.day) {
+            (Some(month), Some(day)) => ("00", format!("{:04}{:02}{:02}", date.year, month, day)),
+            (Some(month), None) => ("01", format!("{:04}{:02}", date.year, month)),
+            _ => ("05", format!("{:04}", date.year)),
+        };
+        writer.start("PublishingDate", &[]);
+        writer.element("PublishingDateRole", &[], "01");
+        writer.element("Date", &[("dateformat", format)], &value);
+        writer.end();
+    }
+    writer.end();
+
+    writer.end();
+}
+
+fn measure(writer: &mut xml::Writer, measure_type: &str, value: &str, unit: &str) {
+    writer.start("Measure", &[]);
+    writer.element("MeasureType", &[], measure_type);
+    writer.element("Measurement", &[], value);
+    writer.element("MeasureUnitCode", &[], unit);
+    writer.end();
+}
+
+fn extent(writer: &mut xml::Writer, extent_type: &str, pages: u32) {
+    writer.start("Extent", &[]);
+    writer.element("ExtentType", &[], extent_type);
+    writer.element("ExtentValue", &[], &pages.to_string());
+    writer.element("ExtentUnit", &[], "03");
+    writer.end();
+}
+
+fn subject(writer: &mut xml::Writer, scheme: &str, text: &str) {
+    writer.start("Subject", &[]);
+    writer.element("SubjectSchemeIdentifier", &[], scheme);
+    if scheme == "20" {
+        writer.element("SubjectHeadingText", &[], text);
+    } else {
+        writer.element("SubjectCode", &[], text.trim());
+    }
+    writer.end();
+}
+
+// ONIX code list 150, product form
+fn product_form(physical_format: &str) -> &'static str {
+    let format = physical_format.to_lowercase();
+    if format.contains("audio") {
+        if format.contains("cd") {
+            "AC"
+        } else if format.contains("cassette") {
+            "AB"
+        } else {
+            "AJ"
+        }
+    } else if format.contains("ebook") || format.contains("e-book") || format.contains("electronic") {
+        "ED"
+    } else if format.contains("hardcover") || format.contains("hardback") || format.contains("hard cover") || format.contains("library binding") {
+        "BB"
+    } else if format.contains("paperback") || format.contains("mass market") || format.contains("softcover") {
+        "BC"
+    } else {
+        "BA"
+    }
+}
+
+// "Illustrated by Quentin Blake" -> ("A12", "Quentin Blake"), unrecognized roles are "other", Z99
+fn contributor_role(contribution: &str) -> (&'static str, &str) {
+    let contribution = contribution.trim();
+    let lowercase = contribution.to_lowercase();
+    let role = ROLES
+        .iter()
+        .find(|(stem, _)| lowercase.contains(stem))
+        .map(|(_, code)| *code)
+        .unwrap_or("Z99");
+
+    if let Some((_, name)) = contribution.split_once(" by ").or_else(|| contribution.split_once(" By ")) {
+        return (role, name.trim());
+    }
+    if let Some((name, _)) = contribution.split_once(" (") {
+        return (role, name.trim());
+    }
+    if let Some((_, name)) = contribution.split_once(':') {
+        return (role, name.trim());
+    }
+
+    (role, contribution)
+}
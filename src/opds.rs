@@ -0,0 +1,389 @@
+use std::collections::BTreeMap;
+
+use serde_json::{json, Map, Value};
+
+use crate::archive::Archive;
+use crate::covers::CoverSize;
+use crate::edition::Edition;
+use crate::entity::Entity;
+use crate::list::Paging;
+use crate::read::{Availability, ReadKey, ReadStatus};
+use crate::xml;
+
+const OPEN_LIBRARY: &str = "https://openlibrary.org";
+
+const ATOM_NAMESPACE: &str = "http://www.w3.org/2005/Atom";
+const DC_TERMS_NAMESPACE: &str = "http://purl.org/dc/terms/";
+const OPDS_NAMESPACE: &str = "http://opds-spec.org/2010/catalog";
+const OPENSEARCH_NAMESPACE: &str = "http://a9.com/-/spec/opensearch/1.1/";
+
+const ATOM_DATETIME: &str = "%Y-%m-%dT%H:%M:%SZ";
+
+const ACQUISITION_FEED: &str = "application/atom+xml;profile=opds-catalog;kind=acquisition";
+const OPDS2_FEED: &str = "application/opds+json";
+
+const OPEN_ACCESS: &str = "http://opds-spec.org/acquisition/open-access";
+const BORROW: &str = "http://opds-spec.org/acquisition/borrow";
+const IMAGE: &str = "http://opds-spec.org/image";
+const THUMBNAIL: &str = "http://opds-spec.org/image/thumbnail";
+
+// A page of entities, e.g. a list's seeds, a subject, an author's works or search results, as an
+// OPDS acquisition feed. `url` is the address the feed is served from, paging links add limit
+// and offset to it.
+#[derive(Clone)]
+pub struct Feed {
+    pub title: String,
+    pub url: String,
+    pub entries: Vec<Entity>,
+    pub paging: Option<Paging>,
+    // Total number of entities across all pages, when known
+    pub total: Option<usize>,
+    // Where download links point, archive.org unless overridden
+    pub archive: Archive,
+    // Client::read_availability for the entries, keyed by ReadKey::OLID request ids
+    pub availability: BTreeMap<String, Availability>,
+}
+
+struct Link {
+    rel: &'static str,
+    href: String,
+    media_type: &'static str,
+}
+
+impl Feed {
+    pub fn new(title: &str, url: &str, entries: Vec<Entity>) -> Self {
+        Self {
+            title: title.to_string(),
+            url: url.to_string(),
+            entries,
+            paging: None,
+            total: None,
+            archive: Archive::default(),
+            availability: BTreeMap::new(),
+        }
+    }
+
+    pub fn with_paging(mut self, paging: Paging, total: Option<usize>) -> Self {
+        self.paging = Some(paging);
+        self.total = total;
+        self
+    }
+
+    // e.g. client.archive().clone(), so downloads go to the same host as the client's requests
+    pub fn with_archive(mut self, archive: Archive) -> Self {
+        self.archive = archive;
+        self
+    }
+
+    // Download links are only offered for editions the availability shows as public, without it
+    // every scanned edition only gets a borrow link
+    pub fn with_availability(mut self, availability: BTreeMap<String, Availability>) -> Self {
+        self.availability = availability;
+        self
+    }
+
+    // OPDS 1.2, an Atom feed
+    pub fn to_opds1(&self) -> String {
+        let mut writer = xml::Writer::new();
+        writer.start(
+            "feed",
+            &[
+                ("xmlns", ATOM_NAMESPACE),
+                ("xmlns:dc", DC_TERMS_NAMESPACE),
+                ("xmlns:opds", OPDS_NAMESPACE),
+                ("xmlns:opensearch", OPENSEARCH_NAMESPACE),
+            ],
+        );
+
+        writer.element("id", &[], &self.page_url());
+        writer.element("title", &[], &self.title);
+        writer.element("updated", &[], &self.updated());
+        writer.start("author", &[]);
+        writer.element("name", &[], "Open Library");
+        writer.element("uri", &[], OPEN_LIBRARY);
+        writer.end();
+
+        for link in self.links(ACQUISITION_FEED) {
+            writer.empty("link", &[("rel", link.rel), ("href", &link.href), ("type", link.media_type)]);
+        }
+
+        if let Some(total) = self.total {
+            writer.element("opensearch:totalResults", &[], &total.to_string());
+        }
+        if let Some(paging) = self.paging {
+            writer.element("opensearch:itemsPerPage", &[], &paging.limit.to_string());
+            writer.element("opensearch:startIndex", &[], &(paging.offset + 1).to_string());
+        }
+
+        for entity in self.entries.iter() {
+            atom_entry(&mut writer, self, entity);
+        }
+
+        writer.finish()
+    }
+
+    // OPDS 2.0, a JSON feed of publications
+    pub fn to_opds2(&self) -> Value {
+        let mut metadata = Map::new();
+        metadata.insert("title".into(), json!(self.title));
+        metadata.insert("modified".into(), json!(self.updated()));
+        if let Some(total) = self.total {
+            metadata.insert("numberOfItems".into(), json!(total));
+        }
+        if let Some(paging) = self.paging {
+            metadata.insert("itemsPerPage".into(), json!(paging.limit));
+            if let Some(page) = paging.offset.checked_div(paging.limit) {
+                metadata.insert("currentPage".into(), json!(page + 1));
+            }
+        }
+
+        let links: Vec<Value> = self
+            .links(OPDS2_FEED)
+            .iter()
+            .map(|link| json!({ "rel": link.rel, "href": link.href, "type": link.media_type }))
+            .collect();
+        let publications: Vec<Value> = self.entries.iter().map(|entity| publication(self, entity)).collect();
+
+        json!({
+            "metadata": metadata,
+            "links": links,
+            "publications": publications,
+        })
+    }
+
+    // self, first, previous, next and last, as far as the paging allows
+    fn links(&self, media_type: &'static str) -> Vec<Link> {
+        let mut links = vec![Link { rel: "self", href: self.page_url(), media_type }];
+
+        let paging = match self.paging {
+            Some(paging) if paging.limit > 0 => paging,
+            _ => return links,
+        };
+
+        links.push(Link { rel: "first", href: self.url_at(Paging::new(paging.limit, 0)), media_type });
+        if paging.offset > 0 {
+            let previous = Paging::new(paging.limit, paging.offset.saturating_sub(paging.limit));
+            links.push(Link { rel: "previous", href: self.url_at(previous), media_type });
+        }
+
+        // Without a total a full page is taken to mean there may be more
+        let has_next = match self.total {
+            Some(total) => paging.offset + paging.limit < total,
+            None => self.entries.len() >= paging.limit,
+        };
+        if has_next {
+            links.push(Link { rel: "next", href: self.url_at(paging.next()), media_type });
+        }
+        if let Some(total) = self.total.filter(|total| *total > 0) {
+            let last = Paging::new(paging.limit, (total - 1) / paging.limit * paging.limit);
+            links.push(Link { rel: "last", href: self.url_at(last), media_type });
+        }
+
+        links
+    }
+
+    // Full access, i.e. public domain, for the edition's own scan
+    fn is_public(&self, entity: &Entity) -> bool {
+        let ocaid = match &entity.edition.ocaid {
+            Some(ocaid) => ocaid,
+            None => return false,
+        };
+
+        self.availability
+            .get(&ReadKey::OLID(entity.olid.clone()).request_id())
+            .into_iter()
+            .flat_map(|availability| availability.items.iter())
+            .any(|item| item.status == ReadStatus::FullAccess && item.get_ocaid().as_ref() == Some(ocaid))
+    }
+
+    fn page_url(&self) -> String {
+        match self.paging {
+            Some(paging) => self.url_at(paging),
+            None => self.url.clone(),
+        }
+    }
+
+    fn url_at(&self, paging: Paging) -> String {
+        let separator = if self.url.contains('?') { '&' } else { '?' };
+
+        format!("{}{}{}", self.url, separator, paging.query())
+    }
+
+    // The most recent change to any entry
+    fn updated(&self) -> String {
+        self.entries
+            .iter()
+            .filter_map(|entity| entity.edition.last_modified.value)
+            .max()
+            .map(|value| value.format(ATOM_DATETIME).to_string())
+            .unwrap_or_else(|| chrono::Utc::now().format(ATOM_DATETIME).to_string())
+    }
+}
+
+fn atom_entry(writer: &mut xml::Writer, feed: &Feed, entity: &Entity) {
+    let edition = &entity.edition;
+    let url = format!("{}/books/{}", OPEN_LIBRARY, entity.olid);
+
+    writer.start("entry", &[]);
+    writer.element("id", &[], &url);
+    writer.element("title", &[], &entity.full_title());
+    // Atom requires updated, a record without a readable timestamp gets the current time
+    let updated = edition.last_modified.value.unwrap_or_else(|| chrono::Utc::now().naive_utc());
+    writer.element("updated", &[], &updated.format(ATOM_DATETIME).to_string());
+
+    for author in entity.authors.iter().filter(|author| !author.name.trim().is_empty()) {
+        writer.start("author", &[]);
+        writer.element("name", &[], author.name.trim());
+        if !author.key.is_empty() {
+            writer.element("uri", &[], &format!("{}{}", OPEN_LIBRARY, author.key));
+        }
+        writer.end();
+    }
+
+    for isbn in edition.isbn13.iter().chain(edition.isbn10.iter()).flatten() {
+        writer.element("dc:identifier", &[], &format!("urn:isbn:{}", isbn.replace(['-', ' '], "")));
+    }
+    for language in edition.languages.iter().flatten() {
+        writer.element("dc:language", &[], language.iso639_1().unwrap_or_else(|| language.code()));
+    }
+    for publisher in edition.publishers.iter().flatten() {
+        writer.element("dc:publisher", &[], publisher.trim());
+    }
+    if let Some(date) = edition.parsed_publish_date() {
+        writer.element("dc:issued", &[], &date.to_iso8601());
+    }
+    for subject in entity.subjects() {
+        writer.empty("category", &[("term", subject), ("label", subject)]);
+    }
+    if let Some(description) = entity.description() {
+        writer.element("summary", &[("type", "text")], description);
+    }
+
+    writer.empty("link", &[("rel", "alternate"), ("href", &url), ("type", "text/html")]);
+    for link in publication_links(feed, entity) {
+        writer.empty("link", &[("rel", link.rel), ("href", &link.href), ("type", link.media_type)]);
+    }
+
+    writer.end();
+}
+
+fn publication(feed: &Feed, entity: &Entity) -> Value {
+    let edition = &entity.edition;
+
+    let mut metadata = Map::new();
+    metadata.insert("@type".into(), json!("http://schema.org/Book"));
+    let identifier = edition
+        .isbn13
+        .iter()
+        .chain(edition.isbn10.iter())
+        .flatten()
+        .next()
+        .map(|isbn| format!("urn:isbn:{}", isbn.replace(['-', ' '], "")))
+        .unwrap_or_else(|| format!("{}/books/{}", OPEN_LIBRARY, entity.olid));
+    metadata.insert("identifier".into(), json!(identifier));
+
+    let (title, subtitle) = entity.title_parts();
+    metadata.insert("title".into(), json!(title));
+    if let Some(subtitle) = subtitle {
+        metadata.insert("subtitle".into(), json!(subtitle));
+    }
+    if let Some(modified) = edition.last_modified.value {
+        metadata.insert("modified".into(), json!(modified.format(ATOM_DATETIME).to_string()));
+    }
+
+    let authors: Vec<Value> = entity
+        .authors
+        .iter()
+        .filter(|author| !author.name.trim().is_empty())
+        .map(|author| json!({ "name": author.name.trim() }))
+        .collect();
+    if !authors.is_empty() {
+        metadata.insert("author".into(), json!(authors));
+    }
+
+    let languages: Vec<&str> = edition
+        .languages
+        .iter()
+        .flatten()
+        .map(|language| language.iso639_1().unwrap_or_else(|| language.code()))
+        .collect();
+    if !languages.is_empty() {
+        metadata.insert("language".into(), json!(languages));
+    }
+    if let Some(publisher) = edition.publishers.iter().flatten().next() {
+        metadata.insert("publisher".into(), json!(publisher.trim()));
+    }
+    if let Some(date) = edition.parsed_publish_date() {
+        metadata.insert("published".into(), json!(date.to_iso8601()));
+    }
+    if let Some(pages) = edition.page_count() {
+        metadata.insert("numberOfPages".into(), json!(pages));
+    }
+    if let Some(description) = entity.description() {
+        metadata.insert("description".into(), json!(description));
+    }
+    let subjects = entity.subjects();
+    if !subjects.is_empty() {
+        metadata.insert("subject".into(), json!(subjects));
+    }
+
+    let mut links = vec![json!({
+        "rel": "self",
+        "href": format!("{}/books/{}", OPEN_LIBRARY, entity.olid),
+        "type": "text/html",
+    })];
+    let mut images = Vec::new();
+    for link in publication_links(feed, entity) {
+        if link.rel == IMAGE || link.rel == THUMBNAIL {
+            images.push(json!({ "href": link.href, "type": link.media_type }));
+        } else {
+            links.push(json!({ "rel": link.rel, "href": link.href, "type": link.media_type }));
+        }
+    }
+
+    let mut publication = Map::new();
+    publication.insert("metadata".into(), Value::Object(metadata));
+    publication.insert("links".into(), json!(links));
+    if !images.is_empty() {
+        publication.insert("images".into(), json!(images));
+    }
+
+    Value::Object(publication)
+}
+
+// Open access downloads for public scans, a borrow link for the others, then the cover and its thumbnail
+fn publication_links(feed: &Feed, entity: &Entity) -> Vec<Link> {
+    let edition = &entity.edition;
+    let mut links = Vec::new();
+
+    if feed.is_public(entity) {
+        let downloads = [("epub", "application/epub+zip"), ("pdf", "application/pdf")];
+        for (extension, media_type) in downloads.iter() {
+            if let Some(href) = edition.archive_download_url(&feed.archive, extension) {
+                links.push(Link { rel: OPEN_ACCESS, href, media_type });
+            }
+        }
+    } else if edition.ocaid.is_some() {
+        links.push(Link {
+            rel: BORROW,
+            href: format!("{}/books/{}/-/borrow", OPEN_LIBRARY, entity.olid),
+            media_type: "text/html",
+        });
+    }
+
+    links.extend(cover_links(edition));
+
+    links
+}
+
+fn cover_links(edition: &Edition) -> Vec<Link> {
+    let mut links = Vec::new();
+    if let Some(href) = edition.cover_url(CoverSize::L) {
+        links.push(Link { rel: IMAGE, href, media_type: "image/jpeg" });
+    }
+    if let Some(href) = edition.cover_url(CoverSize::M) {
+        links.push(Link { rel: THUMBNAIL, href, media_type: "image/jpeg" });
+    }
+
+    links
+}
@@ -0,0 +1,267 @@
+use std::convert::TryFrom;
+
+use crate::edition::Edition;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LengthUnit {
+    Millimeters,
+    Centimeters,
+    Meters,
+    Inches,
+}
+
+impl LengthUnit {
+    fn parse(word: &str) -> Option<Self> {
+        match word.trim_end_matches('.') {
+            "mm" | "millimeter" | "millimeters" | "millimetre" | "millimetres" => Some(LengthUnit::Millimeters),
+            "cm" | "centimeter" | "centimeters" | "centimetre" | "centimetres" => Some(LengthUnit::Centimeters),
+            "m" | "meter" | "meters" | "metre" | "metres" => Some(LengthUnit::Meters),
+            "in" | "inch" | "inches" | "\"" => Some(LengthUnit::Inches),
+            _ => None,
+        }
+    }
+
+    fn millimeters(&self) -> f64 {
+        match self {
+            LengthUnit::Millimeters => 1.0,
+            LengthUnit::Centimeters => 10.0,
+            LengthUnit::Meters => 1000.0,
+            LengthUnit::Inches => 25.4,
+        }
+    }
+}
+
+// Edition.physical_dimensions as "height x width x depth unit", e.g. "24 x 16 x 2 centimeters"
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Dimensions {
+    pub height: f64,
+    pub width: Option<f64>,
+    pub depth: Option<f64>,
+    pub unit: LengthUnit,
+}
+
+impl Dimensions {
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim().to_lowercase();
+
+        // The unit follows the last number, "21 cm" and "9.2 x 6.1 x 0.8 inches" alike
+        let split = text.rfind(|c: char| c.is_ascii_digit())? + 1;
+        let (numbers, unit) = text.split_at(split);
+        let unit = LengthUnit::parse(unit.trim())?;
+
+        let values: Vec<f64> = numbers
+            .split(['x', '×', '*'])
+            .map(|value| parse_number(value.trim()))
+            .collect::<Option<Vec<f64>>>()?;
+
+        match values.as_slice() {
+            [height] => Some(Self { height: *height, width: None, depth: None, unit }),
+            [height, width] => Some(Self { height: *height, width: Some(*width), depth: None, unit }),
+            [height, width, depth] => Some(Self { height: *height, width: Some(*width), depth: Some(*depth), unit }),
+            _ => None,
+        }
+    }
+
+    pub fn convert(&self, unit: LengthUnit) -> Self {
+        let factor = self.unit.millimeters() / unit.millimeters();
+
+        Self {
+            height: self.height * factor,
+            width: self.width.map(|width| width * factor),
+            depth: self.depth.map(|depth| depth * factor),
+            unit,
+        }
+    }
+}
+
+// Edition.weight as sent by Open Library, e.g. "1.2 pounds" or "350 grams", normalized to grams
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Weight {
+    pub grams: f64,
+}
+
+impl Weight {
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim().to_lowercase();
+        let split = text.find(|c: char| !c.is_ascii_digit() && c != '.' && c != ',').unwrap_or(text.len());
+        let (number, unit) = text.split_at(split);
+
+        let value = parse_number(number)?;
+        let grams_per_unit = match unit.trim().trim_end_matches('.') {
+            "g" | "gr" | "gram" | "grams" | "gramme" | "grammes" => 1.0,
+            "kg" | "kilo" | "kilos" | "kilogram" | "kilograms" => 1000.0,
+            "lb" | "lbs" | "pound" | "pounds" => 453.592_37,
+            "oz" | "ounce" | "ounces" => 28.349_523_125,
+            _ => return None,
+        };
+
+        Some(Self { grams: value * grams_per_unit })
+    }
+
+    pub fn kilograms(&self) -> f64 {
+        self.grams / 1000.0
+    }
+
+    pub fn pounds(&self) -> f64 {
+        self.grams / 453.592_37
+    }
+
+    pub fn ounces(&self) -> f64 {
+        self.grams / 28.349_523_125
+    }
+}
+
+// Edition.pagination, e.g. "xii, 345 p." or "x, 220, [4] p."
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pagination {
+    // Roman numbered preliminary pages
+    pub front_matter: u32,
+    // The last page of the arabic numbered sequence
+    pub body: u32,
+    // Bracketed, unnumbered pages
+    pub unnumbered: u32,
+    // "2 v." describes volumes rather than pages
+    pub volumes: Option<u32>,
+}
+
+impl Pagination {
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim().to_lowercase();
+        let mut pagination = Pagination::default();
+        let mut found = false;
+
+        // Illustration and size statements follow a colon or semicolon
+        let extent = text.split([':', ';']).next().unwrap_or_default();
+
+        for segment in extent.split([',', '+']) {
+            let segment = segment.trim();
+            let bracketed = segment.starts_with('[');
+            let words: Vec<&str> = segment
+                .split(|c: char| c.is_whitespace() || c == '[' || c == ']')
+                .filter(|word| !word.is_empty())
+                .collect();
+
+            let first = match words.first() {
+                Some(first) => first.trim_end_matches(['p', '.']),
+                None => continue,
+            };
+
+            if let Ok(number) = first.parse::<u32>() {
+                if words.get(1).is_some_and(|word| word.starts_with('v')) {
+                    pagination.volumes = Some(number);
+                } else if bracketed {
+                    pagination.unnumbered += number;
+                } else if pagination.body == 0 {
+                    pagination.body = number;
+                } else {
+                    pagination.unnumbered += number;
+                }
+                found = true;
+            } else if let Some(number) = parse_roman(first) {
+                pagination.front_matter += number;
+                found = true;
+            }
+        }
+
+        if found {
+            Some(pagination)
+        } else {
+            None
+        }
+    }
+
+    pub fn total(&self) -> u32 {
+        self.front_matter + self.body + self.unnumbered
+    }
+}
+
+impl Edition {
+    pub fn dimensions(&self) -> Option<Dimensions> {
+        self.physical_dimensions.as_deref().and_then(Dimensions::parse)
+    }
+
+    pub fn parsed_weight(&self) -> Option<Weight> {
+        self.weight.as_deref().and_then(Weight::parse)
+    }
+
+    pub fn weight_grams(&self) -> Option<f64> {
+        self.parsed_weight().map(|weight| weight.grams)
+    }
+
+    pub fn parsed_pagination(&self) -> Option<Pagination> {
+        self.pagination.as_deref().and_then(Pagination::parse)
+    }
+
+    // number_of_pages when catalogued, otherwise counted from pagination
+    pub fn page_count(&self) -> Option<u32> {
+        match self.number_of_pages {
+            Some(pages) => Some(pages as u32),
+            None => self.parsed_pagination().map(|pagination| pagination.total()).filter(|pages| *pages > 0),
+        }
+    }
+}
+
+// A comma followed by exactly three digits separates thousands, "1,250 g" and "1,250.5 g",
+// any other comma is a decimal comma as in "1,2 kg"
+fn parse_number(text: &str) -> Option<f64> {
+    let text = text.trim();
+    let thousands = text.split(',').skip(1).all(|group| group.chars().take_while(char::is_ascii_digit).count() == 3);
+
+    if thousands {
+        text.replace(',', "").parse().ok()
+    } else {
+        text.replace(',', ".").parse().ok()
+    }
+}
+
+fn parse_roman(text: &str) -> Option<u32> {
+    if text.is_empty() {
+        return None;
+    }
+
+    let mut total: i64 = 0;
+    let mut previous = 0;
+    for c in text.chars().rev() {
+        let value = match c {
+            'i' => 1,
+            'v' => 5,
+            'x' => 10,
+            'l' => 50,
+            'c' => 100,
+            'd' => 500,
+            'm' => 1000,
+            _ => return None,
+        };
+        if value < previous {
+            total -= value;
+        } else {
+            total += value;
+            previous = value;
+        }
+    }
+
+    // Rejects words like "ill" that only consist of numeral letters
+    let total = u32::try_from(total).ok().filter(|total| *total > 0)?;
+    if to_roman(total) == text {
+        Some(total)
+    } else {
+        None
+    }
+}
+
+fn to_roman(mut number: u32) -> String {
+    const NUMERALS: [(u32, &str); 13] = [
+        (1000, "m"), (900, "cm"), (500, "d"), (400, "cd"), (100, "c"), (90, "xc"),
+        (50, "l"), (40, "xl"), (10, "x"), (9, "ix"), (5, "v"), (4, "iv"), (1, "i"),
+    ];
+
+    let mut roman = String::new();
+    for (value, numeral) in NUMERALS.iter() {
+        while number >= *value {
+            roman.push_str(numeral);
+            number -= value;
+        }
+    }
+
+    roman
+}
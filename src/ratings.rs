@@ -0,0 +1,49 @@
+// https://openlibrary.org/works/OL45804W/ratings.json
+#[derive(Default, Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct Ratings {
+    pub summary: Summary,
+    pub counts: Counts,
+}
+
+// average and sortable are null until a work has been rated at least once
+#[derive(Default, Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct Summary {
+    #[serde(default)]
+    pub average: Option<f64>,
+    pub count: usize,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sortable: Option<f64>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct Counts {
+    #[serde(rename = "1")]
+    pub one: usize,
+    #[serde(rename = "2")]
+    pub two: usize,
+    #[serde(rename = "3")]
+    pub three: usize,
+    #[serde(rename = "4")]
+    pub four: usize,
+    #[serde(rename = "5")]
+    pub five: usize,
+}
+
+impl Counts {
+    pub fn stars(&self, stars: usize) -> Option<usize> {
+        match stars {
+            1 => Some(self.one),
+            2 => Some(self.two),
+            3 => Some(self.three),
+            4 => Some(self.four),
+            5 => Some(self.five),
+            _ => None,
+        }
+    }
+
+    // Index 0 holds the one star count
+    pub fn distribution(&self) -> [usize; 5] {
+        [self.one, self.two, self.three, self.four, self.five]
+    }
+}
@@ -0,0 +1,190 @@
+use std::collections::BTreeMap as Map;
+
+use serde_json::Value;
+
+// Identifiers accepted by https://openlibrary.org/dev/docs/api/read
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReadKey {
+    ISBN(String),
+    OCLC(String),
+    LCCN(String),
+    OLID(String),
+}
+
+impl ReadKey {
+    pub(crate) fn kind(&self) -> &'static str {
+        match self {
+            ReadKey::ISBN(_) => "isbn",
+            ReadKey::OCLC(_) => "oclc",
+            ReadKey::LCCN(_) => "lccn",
+            ReadKey::OLID(_) => "olid",
+        }
+    }
+
+    pub(crate) fn value(&self) -> &str {
+        match self {
+            ReadKey::ISBN(value) | ReadKey::OCLC(value) | ReadKey::LCCN(value) | ReadKey::OLID(value) => value,
+        }
+    }
+
+    // The form used as request id and response key by the multi-key endpoint, e.g. isbn:0596156715
+    pub fn request_id(&self) -> String {
+        format!("{}:{}", self.kind(), self.value())
+    }
+}
+
+// https://openlibrary.org/api/volumes/brief/isbn/0596156715.json
+#[derive(Default, Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct Availability {
+    #[serde(default)]
+    pub records: Map<String, ReadRecord>,
+    #[serde(default)]
+    pub items: Vec<ReadItem>,
+}
+
+impl Availability {
+    // True if any matching item can be read or borrowed right now
+    pub fn readable_now(&self) -> bool {
+        self.items.iter().any(|item| matches!(item.status, ReadStatus::FullAccess | ReadStatus::Lendable))
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct ReadRecord {
+    #[serde(default)]
+    pub isbns: Vec<String>,
+    #[serde(default)]
+    pub issns: Vec<String>,
+    #[serde(default)]
+    pub lccns: Vec<String>,
+    #[serde(default)]
+    pub oclcs: Vec<String>,
+    #[serde(default)]
+    pub olids: Vec<String>,
+    #[serde(default)]
+    #[serde(rename = "publishDates")]
+    pub publish_dates: Vec<String>,
+    #[serde(rename = "recordURL")]
+    pub record_url: String,
+    // The Books API "data" and "details" views of the record
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<Value>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct ReadItem {
+    pub status: ReadStatus,
+    #[serde(rename = "match")]
+    pub match_type: MatchType,
+    #[serde(rename = "itemURL")]
+    pub item_url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ocaid: Option<String>,
+    #[serde(default)]
+    pub enumcron: Value,
+    #[serde(rename = "fromRecord")]
+    pub from_record: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "ol-edition-id")]
+    pub ol_edition_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "ol-work-id")]
+    pub ol_work_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "publishDate")]
+    pub publish_date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contributor: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cover: Option<ReadCover>,
+}
+
+impl ReadItem {
+    // Older responses leave out "ocaid", the identifier is then the last segment of itemURL
+    pub fn get_ocaid(&self) -> Option<String> {
+        if let Some(ocaid) = &self.ocaid {
+            return Some(ocaid.clone());
+        }
+
+        let (_, rest) = self.item_url.split_once("/details/")?;
+        rest.split(['/', '?', '#']).next().filter(|ocaid| !ocaid.is_empty()).map(String::from)
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct ReadCover {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub small: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub medium: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub large: Option<String>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Eq, serde_derive::Serialize, serde_derive::Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum ReadStatus {
+    FullAccess,
+    Lendable,
+    CheckedOut,
+    #[default]
+    Restricted,
+    Other(String),
+}
+
+impl From<String> for ReadStatus {
+    fn from(status: String) -> Self {
+        match status.as_str() {
+            "full access" => ReadStatus::FullAccess,
+            "lendable" => ReadStatus::Lendable,
+            "checked out" => ReadStatus::CheckedOut,
+            "restricted" => ReadStatus::Restricted,
+            _ => ReadStatus::Other(status),
+        }
+    }
+}
+
+impl From<ReadStatus> for String {
+    fn from(status: ReadStatus) -> Self {
+        match status {
+            ReadStatus::FullAccess => String::from("full access"),
+            ReadStatus::Lendable => String::from("lendable"),
+            ReadStatus::CheckedOut => String::from("checked out"),
+            ReadStatus::Restricted => String::from("restricted"),
+            ReadStatus::Other(status) => status,
+        }
+    }
+}
+
+// "exact" when the item is the requested edition, "similar" when it is another edition of the work
+#[derive(Default, Debug, Clone, PartialEq, Eq, serde_derive::Serialize, serde_derive::Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum MatchType {
+    #[default]
+    Exact,
+    Similar,
+    Other(String),
+}
+
+impl From<String> for MatchType {
+    fn from(match_type: String) -> Self {
+        match match_type.as_str() {
+            "exact" => MatchType::Exact,
+            "similar" => MatchType::Similar,
+            _ => MatchType::Other(match_type),
+        }
+    }
+}
+
+impl From<MatchType> for String {
+    fn from(match_type: MatchType) -> Self {
+        match match_type {
+            MatchType::Exact => String::from("exact"),
+            MatchType::Similar => String::from("similar"),
+            MatchType::Other(match_type) => match_type,
+        }
+    }
+}
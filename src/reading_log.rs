@@ -0,0 +1,97 @@
+use crate::client::process_olid_key;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shelf {
+    WantToRead,
+    CurrentlyReading,
+    AlreadyRead,
+}
+
+impl Shelf {
+    pub(crate) fn path(&self) -> &'static str {
+        match self {
+            Shelf::WantToRead => "want-to-read",
+            Shelf::CurrentlyReading => "currently-reading",
+            Shelf::AlreadyRead => "already-read",
+        }
+    }
+}
+
+// https://openlibrary.org/people/mekBot/books/want-to-read.json?page=1
+#[derive(Default, Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct ReadingLog {
+    pub page: usize,
+    #[serde(default)]
+    #[serde(rename = "numFound")]
+    pub num_found: usize,
+    #[serde(default)]
+    pub reading_log_entries: Vec<ReadingLogEntry>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct ReadingLogEntry {
+    pub work: ReadingLogWork,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub logged_edition: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub logged_date: Option<String>,
+}
+
+impl ReadingLogEntry {
+    pub fn work_key(&self) -> &str {
+        &self.work.key
+    }
+
+    // The edition the user logged, None when they only shelved the work
+    pub fn edition_key(&self) -> Option<String> {
+        self.logged_edition.as_ref().map(|key| process_olid_key(key))
+    }
+
+    // The edition Open Library shows the work's cover from, not necessarily one the user chose
+    pub fn cover_edition_key(&self) -> Option<String> {
+        self.work.cover_edition_key.as_ref().map(|key| process_olid_key(key))
+    }
+
+    pub fn title(&self) -> &str {
+        &self.work.title
+    }
+
+    pub fn authors(&self) -> Vec<ReadingLogAuthor> {
+        self.work
+            .author_keys
+            .iter()
+            .zip(self.work.author_names.iter())
+            .map(|(key, name)| ReadingLogAuthor {
+                key: key.clone(),
+                name: name.clone(),
+            })
+            .collect()
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct ReadingLogWork {
+    pub key: String,
+    pub title: String,
+    #[serde(default)]
+    pub author_keys: Vec<String>,
+    #[serde(default)]
+    pub author_names: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub first_publish_year: Option<i32>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub edition_key: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cover_id: Option<isize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cover_edition_key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lending_edition_s: Option<String>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct ReadingLogAuthor {
+    pub key: String,
+    pub name: String,
+}
@@ -0,0 +1,89 @@
+use std::io::{self, Write};
+
+use crate::author::Author;
+use crate::entity::Entity;
+
+impl Entity {
+    // One RIS record, "TY  - BOOK" through "ER  - "
+    pub fn to_ris(&self) -> String {
+        let edition = &self.edition;
+        let mut lines: Vec<(&str, String)> = vec![("TY", String::from("BOOK"))];
+
+        for author in self.authors.iter() {
+            lines.push(("AU", ris_name(author)));
+        }
+
+        lines.push(("TI", self.full_title()));
+        for series in edition.series.iter().flatten() {
+            lines.push(("T2", series.trim().to_string()));
+        }
+
+        let year = edition
+            .parsed_publish_date()
+            .map(|date| date.year)
+            .or_else(|| self.work.first_publish_year());
+        if let Some(year) = year {
+            lines.push(("PY", year.to_string()));
+        }
+
+        for publisher in edition.publishers.iter().flatten() {
+            lines.push(("PB", publisher.trim().to_string()));
+        }
+        for place in edition.publish_places.iter().flatten() {
+            lines.push(("CY", place.trim().to_string()));
+        }
+        for isbn in edition.isbn13.iter().chain(edition.isbn10.iter()).flatten() {
+            lines.push(("SN", isbn.trim().to_string()));
+        }
+        if let Some(edition_name) = edition.edition_statement() {
+            lines.push(("ET", edition_name.to_string()));
+        }
+        for subject in self.subjects() {
+            lines.push(("KW", subject.trim().to_string()));
+        }
+        lines.push(("UR", format!("https://openlibrary.org/books/{}", self.olid)));
+
+        // RIS is line oriented, a value cannot span lines
+        let mut record = String::new();
+        for (tag, value) in lines.iter().filter(|(_, value)| !value.is_empty()) {
+            let value: Vec<&str> = value.split_whitespace().collect();
+            record.push_str(&format!("{}  - {}\r\n", tag, value.join(" ")));
+        }
+        record.push_str("ER  - \r\n");
+
+        record
+    }
+}
+
+// Writes the entities as consecutive RIS records, ready for Zotero or EndNote to import
+pub fn write_ris<W: Write>(writer: &mut W, entities: &[Entity]) -> io::Result<()> {
+    for (index, entity) in entities.iter().enumerate() {
+        if index > 0 {
+            writer.write_all(b"\r\n")?;
+        }
+        writer.write_all(entity.to_ris().as_bytes())?;
+    }
+
+    Ok(())
+}
+
+// "Lastname, Firstname, Suffix", organizations as catalogued
+fn ris_name(author: &Author) -> String {
+    if author.is_organization() {
+        return author.name.trim().to_string();
+    }
+
+    let name = author.personal_name();
+    let mut parts = vec![name.family];
+    if let Some(given) = name.given {
+        parts.push(given);
+    }
+    if let Some(suffix) = name.suffix {
+        if parts.len() == 1 {
+            parts.push(String::new());
+        }
+        parts.push(suffix);
+    }
+
+    parts.join(", ")
+}
@@ -0,0 +1,93 @@
+use surf::http::headers::SET_COOKIE;
+use surf::{Response, StatusCode};
+
+const SESSION_COOKIE: &str = "session";
+
+// https://openlibrary.org/account/login accepts either a form login or the
+// S3 style access/secret keys from https://archive.org/account/s3.php
+pub enum Credentials {
+    Password { username: String, password: String },
+    Keys { access: String, secret: String },
+}
+
+impl Credentials {
+    pub fn password(username: &str, password: &str) -> Self {
+        Credentials::Password {
+            username: String::from(username),
+            password: String::from(password),
+        }
+    }
+
+    pub fn keys(access: &str, secret: &str) -> Self {
+        Credentials::Keys {
+            access: String::from(access),
+            secret: String::from(secret),
+        }
+    }
+}
+
+#[derive(serde_derive::Serialize)]
+pub(crate) struct PasswordForm<'a> {
+    pub username: &'a str,
+    pub password: &'a str,
+    pub remember: bool,
+}
+
+#[derive(serde_derive::Serialize)]
+pub(crate) struct KeysBody<'a> {
+    pub access: &'a str,
+    pub secret: &'a str,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Session {
+    cookie: String,
+}
+
+impl Session {
+    // For reusing a session cookie obtained elsewhere, e.g. from a browser
+    pub fn from_cookie(cookie: &str) -> Self {
+        let value = cookie.trim().trim_start_matches("session=");
+
+        Self {
+            cookie: format!("{}={}", SESSION_COOKIE, value),
+        }
+    }
+
+    pub fn cookie(&self) -> &str {
+        &self.cookie
+    }
+
+    // The session cookie comes back as `session=/people/<user>%2C<timestamp>%2C<hash>; Path=/`
+    pub fn username(&self) -> Option<String> {
+        let value = self.cookie.trim_start_matches("session=").replace("%2C", ",");
+        let path = value.split(',').next()?;
+
+        path.strip_prefix("/people/").map(String::from)
+    }
+
+    pub(crate) fn from_response(res: &Response) -> Result<Self, surf::Error> {
+        let cookies = res.header(SET_COOKIE).into_iter().flatten();
+
+        for cookie in cookies {
+            let pair = cookie.as_str().split(';').next().unwrap_or_default();
+            if let Some(value) = pair.trim().strip_prefix("session=") {
+                if !value.is_empty() {
+                    return Ok(Self::from_cookie(value));
+                }
+            }
+        }
+
+        Err(surf::Error::from_str(StatusCode::Unauthorized, "login failed: no session cookie in response"))
+    }
+}
+
+// Record bodies are sent with the edit comment as an extra "_comment" field
+pub(crate) fn with_comment<T: serde::Serialize>(record: &T, comment: &str) -> Result<serde_json::Value, surf::Error> {
+    let mut json = serde_json::to_value(record)?;
+    if let Some(object) = json.as_object_mut() {
+        object.insert(String::from("_comment"), serde_json::Value::from(comment));
+    }
+
+    Ok(json)
+}
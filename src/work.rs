@@ -1,14 +1,14 @@
-use std::fmt;
-use std::collections::BTreeMap as Map;
-use std::marker::PhantomData;
 use std::str::FromStr;
 
-use serde::{Deserialize, Deserializer};
-use serde::de::{self, Visitor, MapAccess};
+use serde_json::{Map, Value};
 use void::Void;
 
+use crate::canonical::{is_default, read_record, write_record, Elided};
+use crate::date::{Created, LastModified, PartialDate};
+
 #[derive(Default, Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[serde(remote = "Self")]
 pub struct Work {
     pub key: String,
     pub title: String,
@@ -35,7 +35,7 @@ pub struct Work {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<DescEnum>, //It looks like OL59863W a discription type instead of a string
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub notes: Option<String>,
+    pub notes: Option<DescEnum>,
     pub revision: usize,
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "latest_revision")]
@@ -44,19 +44,46 @@ pub struct Work {
     pub created: Option<Created>,
     #[serde(rename = "last_modified")]
     pub last_modified: LastModified,
+    // Fields this crate does not model, kept so that saving a record does not drop them
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+    // Explicit nulls and empty values, kept so that saving a record does not drop them either
+    #[serde(skip)]
+    pub elided: Elided,
+}
+
+impl<'de> serde::Deserialize<'de> for Work {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let (mut record, elided) = read_record(deserializer, Work::deserialize)?;
+        record.elided = elided;
+
+        Ok(record)
+    }
+}
+
+impl serde::Serialize for Work {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        write_record(serializer, &self.elided, |value| Work::serialize(self, value))
+    }
 }
 
 impl Work {
     pub fn get_authors_ids (&self) -> Vec<String> {
         let mut author_ids: Vec<String> = Vec::new();
 
-        let authors = self.authors.as_ref().unwrap();
-
-        for authors in authors.iter() {
+        for authors in self.authors.iter().flatten() {
             author_ids.push(authors.author.key.clone());
         }
 
-        return author_ids;
+        author_ids
+    }
+
+    pub fn parsed_first_publish_date(&self) -> Option<PartialDate> {
+        self.first_publish_date.as_deref().and_then(PartialDate::parse)
+    }
+
+    pub fn first_publish_year(&self) -> Option<i32> {
+        self.parsed_first_publish_date().map(|date| date.year)
     }
 }
 
@@ -64,6 +91,7 @@ impl Work {
 #[serde(rename_all = "camelCase")]
 pub struct Description {
     #[serde(default)]
+    #[serde(skip_serializing_if = "is_default")]
     #[serde(rename = "type")]
     pub type_field: String,
     pub value: String,
@@ -77,6 +105,15 @@ pub enum DescEnum {
     DescType(Description),
 }
 
+impl DescEnum {
+    pub fn text(&self) -> &str {
+        match self {
+            DescEnum::DescString(description) => description,
+            DescEnum::DescType(description) => &description.value,
+        }
+    }
+}
+
 // From https://serde.rs/string-or-struct.html
 impl FromStr for Description {
     type Err = Void;
@@ -89,50 +126,6 @@ impl FromStr for Description {
     }
 }
 
-fn string_or_struct<'de, T, D>(deserializer: D) -> Result<T, D::Error>
-where
-    T: Deserialize<'de> + FromStr<Err = Void>,
-    D: Deserializer<'de>,
-{
-    // This is a Visitor that forwards string types to T's `FromStr` impl and
-    // forwards map types to T's `Deserialize` impl. The `PhantomData` is to
-    // keep the compiler from complaining about T being an unused generic type
-    // parameter. We need T in order to know the Value type for the Visitor
-    // impl.
-    struct StringOrStruct<T>(PhantomData<fn() -> T>);
-
-    impl<'de, T> Visitor<'de> for StringOrStruct<T>
-    where
-        T: Deserialize<'de> + FromStr<Err = Void>,
-    {
-        type Value = T;
-
-        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-            formatter.write_str("string or map")
-        }
-
-        fn visit_str<E>(self, value: &str) -> Result<T, E>
-        where
-            E: de::Error,
-        {
-            Ok(FromStr::from_str(value).unwrap())
-        }
-
-        fn visit_map<M>(self, map: M) -> Result<T, M::Error>
-        where
-            M: MapAccess<'de>,
-        {
-            // `MapAccessDeserializer` is a wrapper that turns a `MapAccess`
-            // into a `Deserializer`, allowing it to be used as the input to T's
-            // `Deserialize` implementation. T then deserializes itself using
-            // the entries from the map visitor.
-            Deserialize::deserialize(de::value::MapAccessDeserializer::new(map))
-        }
-    }
-
-    deserializer.deserialize_any(StringOrStruct(PhantomData))
-}
-
 
 #[derive(Default, Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -144,12 +137,18 @@ pub struct Type {
 #[serde(rename_all = "camelCase")]
 pub struct Authors {
     #[serde(default)]
+    #[serde(skip_serializing_if = "is_default")]
     pub key: String,
     #[serde(default)]
+    #[serde(skip_serializing_if = "is_default")]
     #[serde(rename = "type")]
     pub type_field: Type,
     #[serde(default)]
+    #[serde(skip_serializing_if = "is_default")]
     pub author: Author,
+    // e.g. "role": "Illustrator"
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
@@ -174,26 +173,3 @@ pub struct Id {
     #[serde(rename = "type")]
     pub type_field: String,
 }
-
-#[derive(Default, Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct LcClassifications {
-    #[serde(rename = "$ref")]
-    pub ref_field: String,
-}
-
-#[derive(Default, Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct Created {
-    #[serde(rename = "type")]
-    pub type_field: String,
-    pub value: String,
-}
-
-#[derive(Default, Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct LastModified {
-    #[serde(rename = "type")]
-    pub type_field: String,
-    pub value: String,
-}
@@ -0,0 +1,81 @@
+// A small indenting XML writer shared by the MARCXML, MODS, Dublin Core, ONIX and OPDS exports
+
+pub(crate) struct Writer {
+    buf: String,
+    stack: Vec<String>,
+}
+
+impl Writer {
+    pub fn new() -> Self {
+        Self {
+            buf: String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n"),
+            stack: Vec::new(),
+        }
+    }
+
+    pub fn start(&mut self, name: &str, attributes: &[(&str, &str)]) {
+        self.open_tag(name, attributes);
+        self.buf.push_str(">\n");
+        self.stack.push(name.to_string());
+    }
+
+    pub fn end(&mut self) {
+        if let Some(name) = self.stack.pop() {
+            self.indent();
+            self.buf.push_str(&format!("</{}>\n", name));
+        }
+    }
+
+    pub fn element(&mut self, name: &str, attributes: &[(&str, &str)], text: &str) {
+        self.open_tag(name, attributes);
+        self.buf.push_str(&format!(">{}</{}>\n", escape(text), name));
+    }
+
+    pub fn empty(&mut self, name: &str, attributes: &[(&str, &str)]) {
+        self.open_tag(name, attributes);
+        self.buf.push_str("/>\n");
+    }
+
+    // Closes whatever is still open
+    pub fn finish(mut self) -> String {
+        while !self.stack.is_empty() {
+            self.end();
+        }
+
+        self.buf
+    }
+
+    fn open_tag(&mut self, name: &str, attributes: &[(&str, &str)]) {
+        self.indent();
+        self.buf.push('<');
+        self.buf.push_str(name);
+        for (key, value) in attributes {
+            self.buf.push_str(&format!(" {}=\"{}\"", key, escape(value)));
+        }
+    }
+
+    fn indent(&mut self) {
+        for _ in 0..self.stack.len() {
+            self.buf.push_str("  ");
+        }
+    }
+}
+
+// Escapes markup characters and drops the control characters XML 1.0 does not allow
+pub(crate) fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\t' | '\n' | '\r' => escaped.push(c),
+            c if (c as u32) < 0x20 || c == '\u{FFFE}' || c == '\u{FFFF}' => {}
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
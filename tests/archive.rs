@@ -0,0 +1,23 @@
+mod common;
+
+use openlibrary_client::{Archive, Edition};
+
+#[test]
+fn edition_urls_use_the_given_archive() {
+    let mut edition: Edition = common::from_value(common::edition("OL7353617M"));
+    edition.ocaid = Some(String::from("prideprejudice00aust"));
+    let archive = Archive::new("http://localhost:8080/", "http://localhost:8081");
+
+    assert_eq!(
+        edition.archive_download_url(&archive, "epub").as_deref(),
+        Some("http://localhost:8080/download/prideprejudice00aust/prideprejudice00aust.epub")
+    );
+    assert_eq!(
+        edition.archive_iiif_manifest_url(&archive).as_deref(),
+        Some("http://localhost:8081/iiif/3/prideprejudice00aust/manifest.json")
+    );
+    assert_eq!(
+        edition.archive_details_url(&Archive::default()).as_deref(),
+        Some("https://archive.org/details/prideprejudice00aust")
+    );
+}
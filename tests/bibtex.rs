@@ -0,0 +1,138 @@
+mod common;
+
+use serde_json::json;
+
+use openlibrary_client::{write_bibtex, Author, Edition, Entity, Work};
+
+fn author(name: &str) -> Author {
+    let mut author = common::author("OL21594A");
+    author["name"] = json!(name);
+    common::from_value(author)
+}
+
+fn organization(name: &str) -> Author {
+    let mut author = author(name);
+    author.entity_type = Some(String::from("org"));
+    author
+}
+
+fn edition(title: &str, publish_date: &str) -> Edition {
+    let mut edition: Edition = common::from_value(common::edition("OL7353617M"));
+    edition.title = String::from(title);
+    edition.publish_date = Some(String::from(publish_date));
+    edition
+}
+
+fn work() -> Work {
+    common::from_value(common::work("OL66554W"))
+}
+
+fn book(title: &str, publish_date: &str, authors: Vec<Author>) -> Entity {
+    Entity::with_authors(String::from("OL7353617M"), edition(title, publish_date), work(), authors)
+}
+
+fn field<'a>(entry: &'a str, name: &str) -> Option<&'a str> {
+    let prefix = format!("  {} = ", name);
+    entry.lines().find_map(|line| line.strip_prefix(prefix.as_str())).map(|value| value.trim_end_matches(','))
+}
+
+#[test]
+fn untitled_edition_cites_the_work_title() {
+    let mut work = work();
+    work.subtitle = Some(String::from("A Novel"));
+    let entity = Entity::with_authors(String::from("OL7353617M"), edition("", "1813"), work, vec![author("Jane Austen")]);
+
+    assert_eq!(field(&entity.to_bibtex(), "title"), Some("{Pride and Prejudice: A Novel}"));
+}
+
+#[test]
+fn edition_title_takes_precedence() {
+    let entity = book("Pride & Prejudice", "1813", vec![author("Jane Austen")]);
+
+    assert_eq!(field(&entity.to_bibtex(), "title"), Some(r"{Pride \& Prejudice}"));
+    assert_eq!(field(&entity.get_edition().to_bibtex(), "title"), Some(r"{Pride \& Prejudice}"));
+}
+
+#[test]
+fn latex_specials_are_escaped() {
+    let entity = book(r"Profit & Loss: 100% {Braced} C:\Books_$#~^", "2001", vec![author("Jane Austen")]);
+
+    assert_eq!(
+        field(&entity.to_bibtex(), "title"),
+        Some(r"{Profit \& Loss: 100\% \{Braced\} C:\textbackslash{}Books\_\$\#\textasciitilde{}\textasciicircum{}}")
+    );
+}
+
+#[test]
+fn citation_keys_fold_names_and_skip_articles() {
+    let cases = [
+        ("Cien años de soledad", "1967", "Gabriel García Márquez", "marquez1967cien"),
+        ("The Hobbit", "1937", "J. R. R. Tolkien", "tolkien1937hobbit"),
+        ("L'Assommoir", "1877", "Émile Zola", "zola1877assommoir"),
+        ("Die Verwandlung", "1915", "Franz Kafka", "kafka1915verwandlung"),
+        ("Symphonies", "c1990", "Ludwig van Beethoven", "beethoven1990symphonies"),
+    ];
+
+    for (title, date, name, key) in cases {
+        let entry = book(title, date, vec![author(name)]).to_bibtex();
+        assert!(entry.starts_with(&format!("@book{{{},\n", key)), "{}", entry);
+    }
+}
+
+#[test]
+fn names_use_the_three_part_form() {
+    let entity = book(
+        "Collected Works",
+        "2001",
+        vec![author("Ludwig van Beethoven"), author("Martin Luther King Jr."), author("Austen, Jane")],
+    );
+
+    assert_eq!(
+        field(&entity.to_bibtex(), "author"),
+        Some("{van Beethoven, Ludwig and King, Jr., Martin Luther and Austen, Jane}")
+    );
+}
+
+#[test]
+fn organizations_are_braced() {
+    let entity = book("Numerical Recipes", "1992", vec![organization("Society for Industrial & Applied Mathematics")]);
+    let entry = entity.to_bibtex();
+
+    assert_eq!(field(&entry, "author"), Some(r"{{Society for Industrial \& Applied Mathematics}}"));
+    assert!(entry.starts_with("@book{societyforindustrialappliedmathematics1992numerical,\n"));
+}
+
+#[test]
+fn empty_edition_name_is_left_out() {
+    let mut blank = edition("Emma", "1815");
+    blank.edition_name = Some(String::from("  "));
+    assert_eq!(field(&blank.to_bibtex(), "edition"), None);
+
+    let mut second = edition("Emma", "1815");
+    second.edition_name = Some(String::from("2nd ed."));
+    assert_eq!(field(&second.to_bibtex(), "edition"), Some("{2nd ed.}"));
+}
+
+#[test]
+fn colliding_keys_get_letter_suffixes() {
+    let mut entities: Vec<Entity> = (0..28).map(|_| book("Emma", "1815", vec![author("Jane Austen")])).collect();
+    entities.push(book("Persuasion", "1817", vec![author("Jane Austen")]));
+
+    let mut written = Vec::new();
+    write_bibtex(&mut written, &entities).unwrap();
+    let written = String::from_utf8(written).unwrap();
+
+    let keys: Vec<&str> = written
+        .lines()
+        .filter_map(|line| line.strip_prefix("@book{"))
+        .map(|line| line.trim_end_matches(','))
+        .collect();
+    assert_eq!(keys.len(), 29);
+    assert_eq!(keys[0], "austen1815emmaa");
+    assert_eq!(keys[1], "austen1815emmab");
+    assert_eq!(keys[25], "austen1815emmaz");
+    assert_eq!(keys[26], "austen1815emmaaa");
+    assert_eq!(keys[27], "austen1815emmaab");
+    assert_eq!(keys[28], "austen1817persuasion");
+    assert!(written.contains("}\n\n@book{"));
+}
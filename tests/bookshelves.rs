@@ -0,0 +1,25 @@
+use async_std::task::block_on;
+use mockito::mock;
+
+use openlibrary_client::{Bookshelves, Client};
+
+#[test]
+fn work_bookshelves_unwraps_the_counts() {
+    let _bookshelves = mock("GET", "/works/OL45804W/bookshelves.json")
+        .with_header("Content-Type", "application/json")
+        .with_body(r#"{ "counts": { "want_to_read": 1154, "currently_reading": 43, "already_read": 251 } }"#)
+        .create();
+
+    let client = Client::with_base_url(&mockito::server_url());
+    let bookshelves = block_on(client.work_bookshelves("/works/OL45804W")).unwrap();
+
+    assert_eq!(
+        bookshelves,
+        Bookshelves {
+            want_to_read: 1154,
+            currently_reading: 43,
+            already_read: 251,
+        }
+    );
+    assert_eq!(bookshelves.total(), 1448);
+}
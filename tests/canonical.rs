@@ -0,0 +1,104 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use openlibrary_client::{canonicalize, to_canonical_json, Author, Edition, Note, TableOfContents, Type, Work};
+
+// Reads a record from tests/fixtures and writes it back, which must reproduce the original
+fn round_trip<T: Serialize + DeserializeOwned>(fixture: &str) {
+    let path = format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), fixture);
+    let original = std::fs::read_to_string(&path).unwrap();
+
+    let record: T = serde_json::from_str(&original).unwrap();
+
+    assert_eq!(to_canonical_json(&record).unwrap(), canonicalize(&original).unwrap(), "{}", fixture);
+}
+
+#[test]
+fn edition_round_trips() {
+    round_trip::<Edition>("edition.json");
+}
+
+#[test]
+fn work_round_trips() {
+    round_trip::<Work>("work.json");
+}
+
+#[test]
+fn author_round_trips() {
+    round_trip::<Author>("author.json");
+}
+
+#[test]
+fn null_stays_distinct_from_absent() {
+    let mut edition: Edition = serde_json::from_str(r#"{
+        "key": "/books/OL1M", "title": "", "type": {"key": "/type/edition"}, "ocaid": null,
+        "revision": 1, "created": {"type": "/type/datetime", "value": "2008-04-01T03:28:50"},
+        "last_modified": {"type": "/type/datetime", "value": "2008-04-01T03:28:50"}
+    }"#)
+    .unwrap();
+    let written = to_canonical_json(&edition).unwrap();
+    assert!(written.contains(r#""ocaid":null"#));
+    assert!(!written.contains("subtitle"));
+
+    // A value set since reading replaces the null
+    edition.ocaid = Some(String::from("prideprejudice00aust"));
+    assert!(to_canonical_json(&edition).unwrap().contains(r#""ocaid":"prideprejudice00aust""#));
+}
+
+fn fixture<T: DeserializeOwned>(fixture: &str) -> T {
+    let path = format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), fixture);
+
+    serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap()
+}
+
+#[test]
+fn fixtures_cover_nested_values() {
+    let edition: Edition = fixture("edition.json");
+    let toc = edition.table_of_contents.as_ref().unwrap();
+    assert!(toc.iter().any(|item| item.level == 2 && item.extra.contains_key("class")));
+    assert_eq!(edition.links.as_ref().unwrap().len(), 1);
+    assert!(matches!(&edition.notes, Some(Note::NoteType(note)) if note.type_field == "/type/text"));
+
+    let work: Work = fixture("work.json");
+    assert_eq!(work.links.as_ref().unwrap().len(), 2);
+    assert_eq!(work.authors.as_ref().unwrap()[1].extra["role"], "Illustrator");
+
+    let author: Author = fixture("author.json");
+    let remote_ids = author.remote_ids.as_ref().unwrap();
+    assert_eq!(remote_ids.wikidata, "Q25161");
+    assert_eq!(remote_ids.extra["goodreads"], "4273");
+}
+
+#[test]
+fn array_edits_do_not_misplace_elided_values() {
+    let mut edition: Edition = fixture("edition.json");
+
+    // The first entry was read with "pagenum": null, which must not end up on the inserted entry
+    let introduction = TableOfContents {
+        type_field: Type { key: String::from("/type/toc_item") },
+        title: String::from("Introduction"),
+        ..TableOfContents::default()
+    };
+    edition.table_of_contents.as_mut().unwrap().insert(0, introduction);
+    let written: serde_json::Value = serde_json::from_str(&to_canonical_json(&edition).unwrap()).unwrap();
+    let toc = written["table_of_contents"].as_array().unwrap();
+    assert_eq!(toc.len(), 7);
+    assert_eq!(toc[0]["title"], "Introduction");
+    assert!(!toc[0].as_object().unwrap().contains_key("pagenum"));
+    assert_eq!(toc[2]["pagenum"], "1");
+
+    // Values outside the edited array are still put back
+    assert!(written["subtitle"].is_null() && written.as_object().unwrap().contains_key("subtitle"));
+    assert_eq!(written["lc_classifications"], serde_json::json!([]));
+}
+
+#[test]
+fn unchanged_arrays_keep_their_elided_values() {
+    let mut edition: Edition = fixture("edition.json");
+    edition.table_of_contents.as_mut().unwrap()[1].title = String::from("The Three Farmers: Boggis, Bunce and Bean");
+
+    let written: serde_json::Value = serde_json::from_str(&to_canonical_json(&edition).unwrap()).unwrap();
+    let toc = written["table_of_contents"].as_array().unwrap();
+    assert!(toc[0]["pagenum"].is_null() && toc[0].as_object().unwrap().contains_key("pagenum"));
+    assert_eq!(toc[1]["title"], "The Three Farmers: Boggis, Bunce and Bean");
+}
@@ -0,0 +1,106 @@
+mod common;
+
+use async_std::task::block_on;
+use openlibrary_client::{Catalog, Client, DumpRecord, QueryType};
+use surf::StatusCode;
+
+use common::{author, edition, from_value, work};
+
+#[test]
+fn isbn_lookups_match_either_form() {
+    let mut isbn10_only = edition("OL1M");
+    isbn10_only["isbn_10"] = serde_json::json!(["0-306-40615-2"]);
+    let mut isbn13_only = edition("OL2M");
+    isbn13_only["isbn_13"] = serde_json::json!(["9780140449136"]);
+
+    let catalog = Catalog::open_in_memory().unwrap();
+    catalog.insert(&DumpRecord::Edition(from_value(isbn10_only))).unwrap();
+    catalog.insert(&DumpRecord::Edition(from_value(isbn13_only))).unwrap();
+
+    let found = catalog.edition_by_isbn("978-0-306-40615-7").unwrap().unwrap();
+    assert_eq!(found.key, "/books/OL1M");
+    let found = catalog.edition_by_isbn("0306406152").unwrap().unwrap();
+    assert_eq!(found.key, "/books/OL1M");
+
+    let found = catalog.edition_by_isbn("0140449132").unwrap().unwrap();
+    assert_eq!(found.key, "/books/OL2M");
+}
+
+#[test]
+fn editions_without_works_and_works_without_authors_do_not_panic() {
+    let mut no_works = edition("OL1M");
+    no_works["isbn_13"] = serde_json::json!(["9780306406157"]);
+    no_works.as_object_mut().unwrap().remove("works");
+
+    let mut no_authors = work("OL2W");
+    no_authors.as_object_mut().unwrap().remove("authors");
+    let mut with_lonely_work = edition("OL2M");
+    with_lonely_work["isbn_13"] = serde_json::json!(["9780140449136"]);
+    with_lonely_work["works"] = serde_json::json!([{ "key": "/works/OL2W" }]);
+
+    let catalog = Catalog::open_in_memory().unwrap();
+    catalog.insert(&DumpRecord::Edition(from_value(no_works))).unwrap();
+    catalog.insert(&DumpRecord::Edition(from_value(with_lonely_work))).unwrap();
+    catalog.insert(&DumpRecord::Work(from_value(no_authors))).unwrap();
+    let client = Client::with_catalog(catalog);
+
+    let err = block_on(client.get_work(QueryType::ISBN(String::from("9780306406157")))).unwrap_err();
+    assert_eq!(err.status(), StatusCode::NotFound);
+
+    let err = block_on(client.get_author(QueryType::ISBN(String::from("9780140449136")))).unwrap_err();
+    assert_eq!(err.status(), StatusCode::NotFound);
+
+    let entity = block_on(client.entity_by_isbn("9780140449136")).unwrap();
+    assert!(entity.get_authors().is_empty());
+}
+
+#[test]
+fn entity_by_isbn_collects_all_authors() {
+    let mut with_isbn = edition("OL1M");
+    with_isbn["isbn_10"] = serde_json::json!(["0306406152"]);
+
+    let catalog = Catalog::open_in_memory().unwrap();
+    catalog.insert(&DumpRecord::Edition(from_value(with_isbn))).unwrap();
+    catalog.insert(&DumpRecord::Work(from_value(work("OL66554W")))).unwrap();
+    catalog.insert(&DumpRecord::Author(from_value(author("OL21594A")))).unwrap();
+    let client = Client::with_catalog(catalog);
+
+    let entity = block_on(client.entity_by_isbn("9780306406157")).unwrap();
+    assert_eq!(entity.get_author_name(), "Jane Austen");
+    assert_eq!(entity.get_authors().len(), 1);
+}
+
+#[test]
+fn records_are_keyed_by_their_full_key() {
+    let user = |type_key: &str| DumpRecord::Other {
+        type_key: String::from(type_key),
+        key: String::from("/people/OL1M"),
+        json: serde_json::json!({ "key": "/people/OL1M", "type": { "key": type_key } }),
+    };
+
+    let catalog = Catalog::open_in_memory().unwrap();
+    catalog.insert(&DumpRecord::Edition(from_value(edition("OL1M")))).unwrap();
+    catalog.insert(&user("/type/user")).unwrap();
+    assert_eq!(catalog.edition("OL1M").unwrap().unwrap().key, "/books/OL1M");
+
+    catalog.insert(&user("/type/delete")).unwrap();
+    assert_eq!(catalog.edition("/books/OL1M").unwrap().unwrap().key, "/books/OL1M");
+    assert!(catalog.work("OL1M").unwrap().is_none());
+}
+
+#[test]
+fn language_codes_do_not_collide_with_other_keys() {
+    let record = |type_key: &str, key: &str| DumpRecord::Other {
+        type_key: String::from(type_key),
+        key: String::from(key),
+        json: serde_json::json!({ "key": key, "type": { "key": type_key }, "name": "English", "code": "eng" }),
+    };
+
+    let catalog = Catalog::open_in_memory().unwrap();
+    catalog.insert(&record("/type/language", "/languages/eng")).unwrap();
+    catalog.insert(&record("/type/user", "/people/eng")).unwrap();
+
+    let english = catalog.language("eng").unwrap().unwrap();
+    assert_eq!(english.key, "/languages/eng");
+    assert_eq!(english.language_code().unwrap().iso639_1, Some("en"));
+}
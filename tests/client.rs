@@ -0,0 +1,211 @@
+mod common;
+
+use async_std::task::block_on;
+use mockito::{mock, Matcher};
+use serde_json::json;
+
+use openlibrary_client::{Client, Credentials, Edition, SaveError, Session};
+
+// mockito serves every test from one server, so each test uses its own user and records
+const GEORGE_COOKIE: &str = "session=/people/george08%2C2021-09-22T17%3A22%3A29%2C7f3a9c";
+
+#[test]
+fn login_keeps_the_session_cookie() {
+    let login = mock("POST", "/account/login")
+        .match_body(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("username".into(), "george08".into()),
+            Matcher::UrlEncoded("password".into(), "hunter2".into()),
+        ]))
+        .with_status(303)
+        .with_header("Location", "/")
+        .with_header("Set-Cookie", &format!("{}; Path=/", GEORGE_COOKIE))
+        .create();
+
+    let mut client = Client::with_base_url(&mockito::server_url());
+    block_on(client.login(Credentials::password("george08", "hunter2"))).unwrap();
+
+    login.assert();
+    let session = client.session().unwrap();
+    assert_eq!(session.cookie(), GEORGE_COOKIE);
+    assert_eq!(session.username().as_deref(), Some("george08"));
+}
+
+#[test]
+fn rejected_login_is_an_error() {
+    let login = mock("POST", "/account/login")
+        .match_body(Matcher::UrlEncoded("username".into(), "mallory".into()))
+        .with_status(401)
+        .create();
+
+    let mut client = Client::with_base_url(&mockito::server_url());
+    let err = block_on(client.login(Credentials::password("mallory", "guess"))).unwrap_err();
+
+    login.assert();
+    assert_eq!(err.status(), 401);
+    assert!(client.session().is_none());
+}
+
+#[test]
+fn save_sends_the_session_cookie_and_comment() {
+    let record = common::edition("OL1001M");
+    // Not asserted, surf's redirect middleware sends each GET twice
+    let _current = mock("GET", "/books/OL1001M.json")
+        .with_header("Content-Type", "application/json")
+        .with_body(record.to_string())
+        .create();
+    let save = mock("PUT", "/books/OL1001M.json")
+        .match_header("Cookie", GEORGE_COOKIE)
+        .match_body(Matcher::PartialJson(json!({
+            "key": "/books/OL1001M",
+            "title": "Pride and Prejudice, annotated",
+            "_comment": "fix title",
+        })))
+        .with_status(200)
+        .with_body("{}")
+        .create();
+
+    let mut client = Client::with_base_url(&mockito::server_url());
+    client.set_session(Session::from_cookie(GEORGE_COOKIE));
+    let mut edition: Edition = common::from_value(record);
+    edition.title = String::from("Pride and Prejudice, annotated");
+
+    block_on(client.save_edition(&edition, "fix title")).unwrap();
+
+    save.assert();
+}
+
+#[test]
+fn forbidden_save_is_an_error() {
+    let record = common::edition("OL1002M");
+    let _current = mock("GET", "/books/OL1002M.json")
+        .with_header("Content-Type", "application/json")
+        .with_body(record.to_string())
+        .create();
+    let save = mock("PUT", "/books/OL1002M.json")
+        .with_status(403)
+        .with_body("permission denied")
+        .create();
+
+    let mut client = Client::with_base_url(&mockito::server_url());
+    client.set_session(Session::from_cookie(GEORGE_COOKIE));
+    let edition: Edition = common::from_value(record);
+
+    match block_on(client.save_edition(&edition, "vandalism")) {
+        Err(SaveError::Http(err)) => {
+            assert_eq!(err.status(), 403);
+            assert!(err.to_string().contains("permission denied"));
+        }
+        other => panic!("expected a 403, got {:?}", other.map_err(|err| err.to_string())),
+    }
+    save.assert();
+}
+
+#[test]
+fn save_without_login_is_unauthorized() {
+    let client = Client::with_base_url(&mockito::server_url());
+    let edition: Edition = common::from_value(common::edition("OL1003M"));
+
+    match block_on(client.save_edition(&edition, "anonymous edit")) {
+        Err(SaveError::Http(err)) => assert_eq!(err.status(), 401),
+        other => panic!("expected a 401, got {:?}", other.map_err(|err| err.to_string())),
+    }
+}
+
+// Serves `theirs` as the current record and `base` as the revision the edit started from
+fn serve_revisions(olid: &str, base: &serde_json::Value, theirs: &serde_json::Value) -> Vec<mockito::Mock> {
+    let path = format!("/books/{}.json", olid);
+    vec![
+        mock("GET", path.as_str())
+            .with_header("Content-Type", "application/json")
+            .with_body(theirs.to_string())
+            .create(),
+        mock("GET", format!("{}?v={}", path, base["revision"]).as_str())
+            .with_header("Content-Type", "application/json")
+            .with_body(base.to_string())
+            .create(),
+        mock("PUT", path.as_str()).expect(0).create(),
+    ]
+}
+
+fn newer(record: &serde_json::Value, changes: serde_json::Value) -> serde_json::Value {
+    let mut newer = record.clone();
+    for (field, value) in changes.as_object().unwrap() {
+        newer[field] = value.clone();
+    }
+    newer["revision"] = json!(4);
+    newer["latest_revision"] = json!(4);
+    newer
+}
+
+fn save_conflict(client: &Client, edition: &Edition) -> openlibrary_client::Conflict<Edition> {
+    match block_on(client.save_edition(edition, "fix title")) {
+        Err(SaveError::Conflict(conflict)) => *conflict,
+        other => panic!("expected a conflict, got {:?}", other.map_err(|err| err.to_string())),
+    }
+}
+
+fn logged_in() -> Client {
+    let mut client = Client::with_base_url(&mockito::server_url());
+    client.set_session(Session::from_cookie(GEORGE_COOKIE));
+    client
+}
+
+#[test]
+fn save_over_a_newer_revision_is_a_conflict() {
+    let base = common::edition("OL2001M");
+    let theirs = newer(&base, json!({ "title": "Pride & Prejudice" }));
+    let mocks = serve_revisions("OL2001M", &base, &theirs);
+
+    let mut edition: Edition = common::from_value(base.clone());
+    edition.title = String::from("Pride and Prejudice, annotated");
+    let conflict = save_conflict(&logged_in(), &edition);
+
+    assert_eq!(conflict.base, Some(common::from_value(base)));
+    assert_eq!(conflict.ours, edition);
+    assert_eq!(conflict.theirs, common::from_value(theirs));
+    assert_eq!(conflict.merge().unwrap_err().fields, vec![String::from("title")]);
+    mocks[2].assert();
+}
+
+#[test]
+fn conflict_with_the_same_change_merges_cleanly() {
+    let base = common::edition("OL2002M");
+    let theirs = newer(&base, json!({ "title": "Pride & Prejudice" }));
+    let _mocks = serve_revisions("OL2002M", &base, &theirs);
+
+    let mut edition: Edition = common::from_value(base);
+    edition.title = String::from("Pride & Prejudice");
+    let merged = save_conflict(&logged_in(), &edition).merge().unwrap();
+
+    assert_eq!(merged, common::from_value(theirs));
+}
+
+#[test]
+fn conflict_on_different_fields_merges_both() {
+    let base = common::edition("OL2003M");
+    let theirs = newer(&base, json!({ "publishers": ["Penguin"] }));
+    let _mocks = serve_revisions("OL2003M", &base, &theirs);
+
+    let mut edition: Edition = common::from_value(base);
+    edition.title = String::from("Pride and Prejudice, annotated");
+    let merged = save_conflict(&logged_in(), &edition).merge().unwrap();
+
+    assert_eq!(merged.title, "Pride and Prejudice, annotated");
+    assert_eq!(merged.publishers, Some(vec![String::from("Penguin")]));
+    assert_eq!(merged.revision, 4);
+}
+
+#[test]
+fn conflict_on_an_unsaved_record_has_no_base() {
+    let mut base = common::edition("OL2004M");
+    base["revision"] = json!(0);
+    let theirs = newer(&base, json!({}));
+    let mocks = serve_revisions("OL2004M", &base, &theirs);
+
+    let edition: Edition = common::from_value(base);
+    let conflict = save_conflict(&logged_in(), &edition);
+
+    assert_eq!(conflict.base, None);
+    assert!(!mocks[1].matched());
+    mocks[2].assert();
+}
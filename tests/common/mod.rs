@@ -0,0 +1,48 @@
+// Minimal records in the shape Open Library serves them, shared by the integration tests
+#![allow(dead_code)]
+
+use serde_json::{json, Value};
+
+pub fn datetime(value: &str) -> Value {
+    json!({ "type": "/type/datetime", "value": value })
+}
+
+pub fn edition(olid: &str) -> Value {
+    json!({
+        "key": format!("/books/{}", olid),
+        "title": "Pride and Prejudice",
+        "type": { "key": "/type/edition" },
+        "revision": 3,
+        "created": datetime("2008-04-01T03:28:50.625462"),
+        "last_modified": datetime("2010-04-14T02:53:24.620268"),
+        "works": [{ "key": "/works/OL66554W" }],
+    })
+}
+
+pub fn work(olid: &str) -> Value {
+    json!({
+        "key": format!("/works/{}", olid),
+        "title": "Pride and Prejudice",
+        "type": { "key": "/type/work" },
+        "revision": 5,
+        "created": datetime("2009-10-15T11:32:41.432422"),
+        "last_modified": datetime("2020-08-26T23:13:04.564789"),
+        "authors": [{ "author": { "key": "/authors/OL21594A" }, "type": { "key": "/type/author_role" } }],
+    })
+}
+
+pub fn author(olid: &str) -> Value {
+    json!({
+        "key": format!("/authors/{}", olid),
+        "name": "Jane Austen",
+        "type": { "key": "/type/author" },
+        "revision": 2,
+        "birth_date": "16 December 1775",
+        "death_date": "18 July 1817",
+        "last_modified": datetime("2021-12-26T21:02:50.346853"),
+    })
+}
+
+pub fn from_value<T: serde::de::DeserializeOwned>(value: Value) -> T {
+    serde_json::from_value(value).unwrap()
+}
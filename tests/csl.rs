@@ -0,0 +1,166 @@
+mod common;
+
+use openlibrary_client::{Author, CitationStyle, CslDate, CslItem, CslName, Edition, Entity, Work};
+
+fn entity(edition_title: &str) -> Entity {
+    let mut edition: Edition = common::from_value(common::edition("OL7353617M"));
+    edition.title = String::from(edition_title);
+    let mut work: Work = common::from_value(common::work("OL66554W"));
+    work.subtitle = Some(String::from("A Novel"));
+    let author: Author = common::from_value(common::author("OL21594A"));
+
+    Entity::new(String::from("OL7353617M"), edition, work, author)
+}
+
+#[test]
+fn untitled_edition_cites_the_work_title() {
+    let entity = entity("");
+
+    assert_eq!(entity.to_csl_json().title, "Pride and Prejudice: A Novel");
+}
+
+#[test]
+fn edition_title_takes_precedence() {
+    let entity = entity("Pride & Prejudice");
+
+    assert_eq!(entity.to_csl_json().title, "Pride & Prejudice");
+}
+
+fn person(family: &str, given: &str) -> CslName {
+    CslName {
+        family: Some(String::from(family)),
+        given: Some(String::from(given)),
+        ..CslName::default()
+    }
+}
+
+fn item(title: &str, author: Vec<CslName>, year: Option<i32>, publisher: Option<&str>, edition: Option<&str>) -> CslItem {
+    CslItem {
+        id: String::from("OL7353617M"),
+        type_field: String::from("book"),
+        title: String::from(title),
+        author,
+        issued: year.map(|year| CslDate {
+            date_parts: Some(vec![vec![year]]),
+            ..CslDate::default()
+        }),
+        publisher: publisher.map(String::from),
+        edition: edition.map(String::from),
+        ..CslItem::default()
+    }
+}
+
+fn one_author() -> CslItem {
+    let mut item = item("Pride and Prejudice", vec![person("Austen", "Jane")], Some(1813), Some("T. Egerton"), None);
+    item.publisher_place = Some(String::from("London"));
+    item
+}
+
+fn two_authors() -> CslItem {
+    let king = CslName {
+        suffix: Some(String::from("Jr.")),
+        ..person("King", "Martin Luther")
+    };
+
+    item("Letters", vec![person("Austen", "Jane"), king], Some(1998), Some("Penguin"), Some("2nd edition"))
+}
+
+fn three_authors() -> CslItem {
+    let authors = vec![person("Austen", "Jane"), person("Brontë", "Charlotte"), person("Eliot", "George")];
+
+    item("Three Novels: A Collection", authors, None, None, Some("2nd ed., rev."))
+}
+
+fn organization() -> CslItem {
+    let association = CslName {
+        literal: Some(String::from("Modern Language Association")),
+        ..CslName::default()
+    };
+
+    item("MLA Handbook", vec![association], Some(2021), Some("Modern Language Association of America"), Some("9th"))
+}
+
+#[test]
+fn apa() {
+    let style = CitationStyle::Apa;
+    let cases = [
+        (one_author(), "(Austen, 1813)", "Austen, J. (1813). Pride and Prejudice. T. Egerton."),
+        (two_authors(), "(Austen & King, 1998)", "Austen, J., & King, M. L., Jr. (1998). Letters (2nd ed.). Penguin."),
+        (three_authors(), "(Austen et al., n.d.)", "Austen, J., Brontë, C., & Eliot, G. (n.d.). Three Novels: A Collection (2nd ed., rev.)."),
+        (organization(), "(Modern Language Association, 2021)", "Modern Language Association. (2021). MLA Handbook (9th ed.). Modern Language Association of America."),
+    ];
+
+    for (item, citation, reference) in cases.iter() {
+        assert_eq!(style.citation(item), *citation);
+        assert_eq!(style.reference(item), *reference);
+    }
+}
+
+#[test]
+fn mla() {
+    let style = CitationStyle::Mla;
+    let cases = [
+        (one_author(), "(Austen)", "Austen, Jane. Pride and Prejudice. T. Egerton, 1813."),
+        (two_authors(), "(Austen and King)", "Austen, Jane, and Martin Luther King Jr. Letters. 2nd ed., Penguin, 1998."),
+        (three_authors(), "(Austen et al.)", "Austen, Jane, et al. Three Novels: A Collection. 2nd ed., rev."),
+        (organization(), "(Modern Language Association)", "Modern Language Association. MLA Handbook. 9th ed., Modern Language Association of America, 2021."),
+    ];
+
+    for (item, citation, reference) in cases.iter() {
+        assert_eq!(style.citation(item), *citation);
+        assert_eq!(style.reference(item), *reference);
+    }
+}
+
+#[test]
+fn chicago() {
+    let style = CitationStyle::Chicago;
+    let cases = [
+        (one_author(), "(Austen 1813)", "Austen, Jane. 1813. Pride and Prejudice. London: T. Egerton."),
+        (two_authors(), "(Austen and King 1998)", "Austen, Jane, and Martin Luther King Jr. 1998. Letters. 2nd ed. Penguin."),
+        (three_authors(), "(Austen et al. n.d.)", "Austen, Jane, Charlotte Brontë, and George Eliot. n.d. Three Novels: A Collection. 2nd ed., rev."),
+        (organization(), "(Modern Language Association 2021)", "Modern Language Association. 2021. MLA Handbook. 9th ed. Modern Language Association of America."),
+    ];
+
+    for (item, citation, reference) in cases.iter() {
+        assert_eq!(style.citation(item), *citation);
+        assert_eq!(style.reference(item), *reference);
+    }
+}
+
+#[test]
+fn anonymous_works_are_cited_by_title() {
+    let anonymous = item("Beowulf: A New Verse Translation", Vec::new(), None, None, None);
+
+    assert_eq!(CitationStyle::Apa.citation(&anonymous), "(\"Beowulf\", n.d.)");
+    assert_eq!(CitationStyle::Mla.citation(&anonymous), "(\"Beowulf\")");
+    assert_eq!(CitationStyle::Apa.reference(&anonymous), "(n.d.). Beowulf: A New Verse Translation.");
+}
+
+#[test]
+fn bibliography_sorts_by_author_then_year() {
+    let items = [organization(), two_authors(), one_author(), three_authors()];
+
+    assert_eq!(
+        CitationStyle::Apa.bibliography(&items),
+        [
+            "Austen, J. (1813). Pride and Prejudice. T. Egerton.",
+            "Austen, J., Brontë, C., & Eliot, G. (n.d.). Three Novels: A Collection (2nd ed., rev.).",
+            "Austen, J., & King, M. L., Jr. (1998). Letters (2nd ed.). Penguin.",
+            "Modern Language Association. (2021). MLA Handbook (9th ed.). Modern Language Association of America.",
+        ]
+        .join("\n")
+    );
+}
+
+#[test]
+fn empty_edition_name_is_left_out() {
+    let mut edition: Edition = common::from_value(common::edition("OL7353617M"));
+    edition.edition_name = Some(String::from(" "));
+    let work: Work = common::from_value(common::work("OL66554W"));
+    let author: Author = common::from_value(common::author("OL21594A"));
+
+    let item = Entity::new(String::from("OL7353617M"), edition, work, author).to_csl_json();
+    assert_eq!(item.edition, None);
+    assert_eq!(CitationStyle::Apa.reference(&item), "Austen, J. (n.d.). Pride and Prejudice.");
+}
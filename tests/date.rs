@@ -0,0 +1,127 @@
+mod common;
+
+use serde_json::json;
+
+use chrono::NaiveDate;
+use openlibrary_client::{Datetime, Edition, PartialDate, Work};
+
+#[test]
+fn unreadable_timestamp_keeps_the_record() {
+    let mut record = common::edition("OL7353617M");
+    record["last_modified"] = common::datetime("2010-04-14T02:53:24Z+0000");
+    record["created"] = common::datetime("2008-04-01T03:28:50.625");
+
+    let edition: Edition = common::from_value(record.clone());
+    assert_eq!(edition.last_modified.value, None);
+    assert_eq!(edition.last_modified.to_string(), "2010-04-14T02:53:24Z+0000");
+    assert_eq!(edition.created.value.unwrap().to_string(), "2008-04-01 03:28:50.625");
+
+    // Written back exactly as read
+    let written = serde_json::to_value(&edition).unwrap();
+    assert_eq!(written["last_modified"], record["last_modified"]);
+    assert_eq!(written["created"], record["created"]);
+}
+
+#[test]
+fn new_datetime_is_formatted_like_open_library() {
+    let datetime = Datetime::parse("2008-04-01 03:28:50.625462").unwrap();
+
+    assert_eq!(
+        serde_json::to_value(&datetime).unwrap(),
+        json!({ "type": "/type/datetime", "value": "2008-04-01T03:28:50.625462" })
+    );
+}
+
+#[test]
+fn changed_datetime_is_written_in_open_library_format() {
+    let mut edition: Edition = common::from_value(common::edition("OL7353617M"));
+    edition.last_modified.value = NaiveDate::from_ymd_opt(2021, 9, 22).unwrap().and_hms_opt(17, 22, 29);
+
+    let written = serde_json::to_value(&edition).unwrap();
+    assert_eq!(written["last_modified"], common::datetime("2021-09-22T17:22:29"));
+    assert_eq!(written["created"], common::datetime("2008-04-01T03:28:50.625462"));
+}
+
+fn date(year: i32, month: Option<u32>, day: Option<u32>, circa: bool, uncertain: bool) -> Option<PartialDate> {
+    Some(PartialDate { year, month, day, circa, uncertain })
+}
+
+#[test]
+fn partial_dates_parse_as_catalogued() {
+    let cases = [
+        ("1998", date(1998, None, None, false, false)),
+        ("c1998", date(1998, None, None, true, false)),
+        ("ca. 1998", date(1998, None, None, true, false)),
+        ("1998?", date(1998, None, None, false, true)),
+        ("March 2001", date(2001, Some(3), None, false, false)),
+        ("March 15, 2001", date(2001, Some(3), Some(15), false, false)),
+        ("15th March 2001", date(2001, Some(3), Some(15), false, false)),
+        ("February 30, 2001", date(2001, Some(2), None, false, false)),
+        ("2001-03-15", date(2001, Some(3), Some(15), false, false)),
+        ("2001-03", date(2001, Some(3), None, false, false)),
+        ("1st ed. 1985", date(1985, None, None, false, false)),
+        ("1985-86", date(1985, None, None, false, false)),
+        ("[199-?]", date(1990, None, None, false, true)),
+        ("19--", date(1900, None, None, false, true)),
+        ("", None),
+        ("n.d.", None),
+        ("[s.d.]", None),
+    ];
+
+    for (text, expected) in cases {
+        assert_eq!(PartialDate::parse(text), expected, "{:?}", text);
+    }
+}
+
+#[test]
+fn partial_dates_format_and_start() {
+    let cases = [
+        ("c1998", "1998", "ca. 1998", (1998, 1, 1)),
+        ("March 2001", "2001-03", "2001-03", (2001, 3, 1)),
+        ("15 March 2001", "2001-03-15", "2001-03-15", (2001, 3, 15)),
+        ("[199-?]", "1990", "1990?", (1990, 1, 1)),
+    ];
+
+    for (text, iso8601, display, (year, month, day)) in cases {
+        let date = PartialDate::parse(text).unwrap();
+        assert_eq!(date.to_iso8601(), iso8601, "{:?}", text);
+        assert_eq!(date.to_string(), display, "{:?}", text);
+        assert_eq!(date.first_day(), NaiveDate::from_ymd_opt(year, month, day), "{:?}", text);
+    }
+}
+
+#[test]
+fn partial_dates_sort_chronologically() {
+    let mut dates: Vec<PartialDate> = ["2001-03-15", "1998", "March 2001", "c1998", "2001"]
+        .iter()
+        .filter_map(|text| PartialDate::parse(text))
+        .collect();
+    dates.sort();
+
+    let sorted: Vec<String> = dates.iter().map(|date| date.to_string()).collect();
+    assert_eq!(sorted, ["1998", "ca. 1998", "2001", "2001-03", "2001-03-15"]);
+}
+
+#[test]
+fn publish_years_come_from_the_parsed_dates() {
+    let mut record = common::edition("OL7353617M");
+    let edition: Edition = common::from_value(record.clone());
+    assert_eq!(edition.publish_year(), None);
+
+    record["publish_date"] = json!("c1998");
+    let edition: Edition = common::from_value(record.clone());
+    assert_eq!(edition.publish_year(), Some(1998));
+
+    record["publish_date"] = json!("n.d.");
+    let edition: Edition = common::from_value(record);
+    assert_eq!(edition.publish_year(), None);
+
+    let mut record = common::work("OL66554W");
+    let work: Work = common::from_value(record.clone());
+    assert_eq!(work.first_publish_year(), None);
+
+    record["first_publish_date"] = json!("January 28, 1813");
+    let work: Work = common::from_value(record);
+    assert_eq!(work.first_publish_year(), Some(1813));
+    assert_eq!(work.parsed_first_publish_date().unwrap().to_iso8601(), "1813-01-28");
+}
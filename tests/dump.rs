@@ -0,0 +1,123 @@
+use std::fs::{self, File};
+use std::io::{Cursor, Write};
+
+use flate2::write::GzEncoder;
+use flate2::Compression;
+
+use openlibrary_client::{DumpErrorKind, DumpReader, DumpRecord};
+
+const LAST_MODIFIED: &str = r#"{"type": "/type/datetime", "value": "2008-04-01T03:28:50.625462"}"#;
+
+fn row(type_key: &str, key: &str, json: String) -> Vec<u8> {
+    format!("{}\t{}\t1\t2008-04-01T03:28:50.625462\t{}\n", type_key, key, json).into_bytes()
+}
+
+fn author_row(key: &str, name: &str) -> Vec<u8> {
+    let json = format!(
+        r#"{{"key": "{}", "name": "{}", "type": {{"key": "/type/author"}}, "revision": 1, "last_modified": {}}}"#,
+        key, name, LAST_MODIFIED
+    );
+
+    row("/type/author", key, json)
+}
+
+fn edition_row(key: &str, title: &str) -> Vec<u8> {
+    let json = format!(
+        r#"{{"key": "{}", "title": "{}", "type": {{"key": "/type/edition"}}, "works": [{{"key": "/works/OL1W"}}], "revision": 1, "created": {}, "last_modified": {}}}"#,
+        key, title, LAST_MODIFIED, LAST_MODIFIED
+    );
+
+    row("/type/edition", key, json)
+}
+
+fn work_row(key: &str, title: &str) -> Vec<u8> {
+    let json = format!(
+        r#"{{"key": "{}", "title": "{}", "type": {{"key": "/type/work"}}, "authors": [], "revision": 1, "created": {}, "last_modified": {}}}"#,
+        key, title, LAST_MODIFIED, LAST_MODIFIED
+    );
+
+    row("/type/work", key, json)
+}
+
+// A dump of every record type, with a redirect that none of the typed iterators yield
+fn mixed_dump() -> Vec<u8> {
+    let mut dump = edition_row("/books/OL1M", "Emma");
+    dump.extend(work_row("/works/OL1W", "Emma"));
+    dump.extend(author_row("/authors/OL1A", "Jane Austen"));
+    dump.extend(row("/type/redirect", "/books/OL2M", String::from(r#"{"key": "/books/OL2M", "location": "/books/OL1M"}"#)));
+    dump.extend(edition_row("/books/OL3M", "Persuasion"));
+    dump
+}
+
+#[test]
+fn invalid_utf8_row_is_reported_and_iteration_continues() {
+    let mut dump = author_row("/authors/OL1A", "Jane Austen");
+    dump.extend_from_slice(b"/type/author\t/authors/OL2A\t1\t2008-04-01T03:28:50\t{\"name\": \"Bront\xeb\"}\n");
+    dump.extend(author_row("/authors/OL3A", "Mary Shelley"));
+
+    let records: Vec<_> = DumpReader::new(Cursor::new(dump)).collect();
+    assert_eq!(records.len(), 3);
+
+    match &records[0] {
+        Ok(DumpRecord::Author(author)) => assert_eq!(author.name, "Jane Austen"),
+        other => panic!("unexpected first record {:?}", other),
+    }
+
+    let err = records[1].as_ref().unwrap_err();
+    assert_eq!(err.line(), 2);
+    assert!(matches!(err.kind(), DumpErrorKind::Utf8(_)));
+
+    match &records[2] {
+        Ok(DumpRecord::Author(author)) => assert_eq!(author.name, "Mary Shelley"),
+        other => panic!("unexpected third record {:?}", other),
+    }
+}
+
+#[test]
+fn malformed_rows_do_not_stop_iteration() {
+    let mut dump = b"/type/author\t/authors/OL1A\n".to_vec();
+    dump.extend(author_row("/authors/OL2A", "Mary Shelley"));
+
+    let records: Vec<_> = DumpReader::new(Cursor::new(dump)).collect();
+    assert_eq!(records.len(), 2);
+    assert!(matches!(records[0].as_ref().unwrap_err().kind(), DumpErrorKind::MissingColumns(2)));
+    assert_eq!(records[1].as_ref().unwrap().key(), "/authors/OL2A");
+}
+
+#[test]
+fn typed_iterators_keep_only_their_records() {
+    let editions: Vec<String> = DumpReader::new(Cursor::new(mixed_dump())).editions().map(|edition| edition.unwrap().key).collect();
+    assert_eq!(editions, ["/books/OL1M", "/books/OL3M"]);
+
+    let works: Vec<String> = DumpReader::new(Cursor::new(mixed_dump())).works().map(|work| work.unwrap().key).collect();
+    assert_eq!(works, ["/works/OL1W"]);
+
+    let authors: Vec<String> = DumpReader::new(Cursor::new(mixed_dump())).authors().map(|author| author.unwrap().key).collect();
+    assert_eq!(authors, ["/authors/OL1A"]);
+}
+
+#[test]
+fn typed_iterators_pass_errors_through() {
+    let mut dump = b"/type/edition\t/books/OL1M\n".to_vec();
+    dump.extend(mixed_dump());
+
+    let editions: Vec<_> = DumpReader::new(Cursor::new(dump)).editions().collect();
+    assert_eq!(editions.len(), 3);
+    assert_eq!(editions[0].as_ref().unwrap_err().line(), 1);
+}
+
+#[test]
+fn open_reads_a_gzipped_dump() {
+    let path = std::env::temp_dir().join(format!("ol_dump_editions_{}.txt.gz", std::process::id()));
+    let mut encoder = GzEncoder::new(File::create(&path).unwrap(), Compression::default());
+    encoder.write_all(&mixed_dump()).unwrap();
+    encoder.finish().unwrap();
+
+    let records: Vec<DumpRecord> = DumpReader::open(&path).unwrap().map(Result::unwrap).collect();
+    fs::remove_file(&path).unwrap();
+
+    let keys: Vec<&str> = records.iter().map(DumpRecord::key).collect();
+    assert_eq!(keys, ["/books/OL1M", "/works/OL1W", "/authors/OL1A", "/books/OL2M", "/books/OL3M"]);
+    assert!(matches!(&records[0], DumpRecord::Edition(edition) if edition.title == "Emma"));
+    assert!(DumpReader::open(std::env::temp_dir().join("ol_dump_missing.txt.gz")).is_err());
+}
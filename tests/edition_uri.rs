@@ -0,0 +1,78 @@
+mod common;
+
+use async_std::task::block_on;
+use mockito::mock;
+
+use openlibrary_client::{Client, QueryType};
+
+fn serve(path: &str, body: &str) -> mockito::Mock {
+    mock("GET", path)
+        .with_header("Content-Type", "application/json")
+        .with_body(body)
+        .create()
+}
+
+fn resolves_to(query_type: QueryType, olid: &str) {
+    let client = Client::with_base_url(&mockito::server_url());
+    let edition = block_on(client.get_edition(query_type)).unwrap();
+
+    assert_eq!(edition.key, format!("/books/{}", olid));
+}
+
+#[test]
+fn oclc_goes_through_the_books_api() {
+    let _bibkeys = serve(
+        "/api/books?bibkeys=OCLC:297222669&format=json&jscmd=data",
+        r#"{ "OCLC:297222669": { "key": "/books/OL3001M", "title": "Pride and Prejudice", "url": "https://openlibrary.org/books/OL3001M/Pride_and_Prejudice" } }"#,
+    );
+    let _edition = serve("/q/OL3001M.json", &common::edition("OL3001M").to_string());
+
+    resolves_to(QueryType::OCLC(String::from("297222669")), "OL3001M");
+}
+
+#[test]
+fn lccn_goes_through_the_books_api() {
+    let _bibkeys = serve(
+        "/api/books?bibkeys=LCCN:2001022779&format=json&jscmd=data",
+        r#"{ "LCCN:2001022779": { "key": "/books/OL3002M" } }"#,
+    );
+    let _edition = serve("/q/OL3002M.json", &common::edition("OL3002M").to_string());
+
+    resolves_to(QueryType::LCCN(String::from("2001022779")), "OL3002M");
+}
+
+#[test]
+fn goodreads_goes_through_search() {
+    let _search = serve(
+        "/search.json?q=id_goodreads:1885&fields=key,editions",
+        r#"{ "numFound": 1, "docs": [{ "key": "/works/OL66554W", "editions": { "numFound": 1, "docs": [{ "key": "/books/OL3003M" }] } }] }"#,
+    );
+    let _edition = serve("/q/OL3003M.json", &common::edition("OL3003M").to_string());
+
+    resolves_to(QueryType::Goodreads(String::from("1885")), "OL3003M");
+}
+
+#[test]
+fn librarything_goes_through_search() {
+    let _search = serve(
+        "/search.json?q=id_librarything:1054&fields=key,editions",
+        r#"{ "numFound": 1, "docs": [{ "key": "/works/OL66554W", "editions": { "numFound": 1, "docs": [{ "key": "/books/OL3004M" }] } }] }"#,
+    );
+    let _edition = serve("/q/OL3004M.json", &common::edition("OL3004M").to_string());
+
+    resolves_to(QueryType::LibraryThing(String::from("1054")), "OL3004M");
+}
+
+#[test]
+fn unknown_identifier_is_not_found() {
+    let _search = serve(
+        "/search.json?q=id_goodreads:0&fields=key,editions",
+        r#"{ "numFound": 0, "docs": [] }"#,
+    );
+
+    let client = Client::with_base_url(&mockito::server_url());
+    let err = block_on(client.get_edition(QueryType::Goodreads(String::from("0")))).unwrap_err();
+
+    assert_eq!(err.status(), 404);
+    assert!(err.to_string().contains("Goodreads ID 0"));
+}
@@ -0,0 +1,115 @@
+mod common;
+
+use serde_json::{json, Value};
+
+use openlibrary_client::{Author, Edition, Entity, Work};
+
+fn json_ld(edition: Value, author: Value) -> Value {
+    let edition: Edition = common::from_value(edition);
+    let work: Work = common::from_value(common::work("OL66554W"));
+    let author: Author = common::from_value(author);
+
+    Entity::with_authors(String::from("OL7353617M"), edition, work, vec![author]).to_json_ld()
+}
+
+#[test]
+fn work_example_describes_the_edition() {
+    let mut edition = common::edition("OL7353617M");
+    edition["isbn_13"] = json!(["978-0-14-143951-8"]);
+    edition["isbn_10"] = json!(["0141439513"]);
+    edition["number_of_pages"] = json!(480);
+    edition["physical_format"] = json!("Paperback");
+    edition["edition_name"] = json!("Penguin Classics ed.");
+    edition["publishers"] = json!(["Penguin Books", "Penguin Classics"]);
+    edition["publish_date"] = json!("April 2003");
+    edition["covers"] = json!([-1, 8231856]);
+
+    let book = json_ld(edition, common::author("OL21594A"));
+
+    assert_eq!(book["@context"], "https://schema.org");
+    assert_eq!(book["@id"], "https://openlibrary.org/works/OL66554W");
+    assert_eq!(book["image"], "https://covers.openlibrary.org/b/id/8231856-L.jpg");
+    assert_eq!(
+        book["workExample"],
+        json!([{
+            "@type": "Book",
+            "@id": "https://openlibrary.org/books/OL7353617M",
+            "url": "https://openlibrary.org/books/OL7353617M",
+            "name": "Pride and Prejudice",
+            "isbn": "9780141439518",
+            "numberOfPages": 480,
+            "bookFormat": "https://schema.org/Paperback",
+            "bookEdition": "Penguin Classics ed.",
+            "publisher": { "@type": "Organization", "name": "Penguin Books" },
+            "datePublished": "2003-04",
+            "image": "https://covers.openlibrary.org/b/id/8231856-L.jpg",
+        }])
+    );
+}
+
+#[test]
+fn blank_edition_name_is_left_out() {
+    let mut edition = common::edition("OL7353617M");
+    edition["edition_name"] = json!("  ");
+
+    let book = json_ld(edition, common::author("OL21594A"));
+
+    assert!(book["workExample"][0].get("bookEdition").is_none());
+}
+
+#[test]
+fn in_language_is_a_single_value_or_an_array() {
+    let mut edition = common::edition("OL7353617M");
+    edition["languages"] = json!([{ "key": "/languages/eng" }]);
+    let book = json_ld(edition.clone(), common::author("OL21594A"));
+    assert_eq!(book["workExample"][0]["inLanguage"], json!("en"));
+
+    // Codes without a two letter form stay as they are
+    edition["languages"] = json!([{ "key": "/languages/eng" }, { "key": "/languages/fre" }, { "key": "/languages/ang" }]);
+    let book = json_ld(edition.clone(), common::author("OL21594A"));
+    assert_eq!(book["workExample"][0]["inLanguage"], json!(["en", "fr", "ang"]));
+
+    edition["languages"] = json!([]);
+    let book = json_ld(edition, common::author("OL21594A"));
+    assert!(book["workExample"][0].get("inLanguage").is_none());
+}
+
+#[test]
+fn authors_link_to_their_authority_records() {
+    let mut author = common::author("OL21594A");
+    author["remote_ids"] = json!({ "wikidata": "Q36322", "viaf": "102333412", "isni": "0000 0001 2283 4400" });
+    author["wikipedia"] = json!(" https://en.wikipedia.org/wiki/Jane_Austen ");
+
+    let book = json_ld(common::edition("OL7353617M"), author);
+
+    assert_eq!(
+        book["author"],
+        json!([{
+            "@type": "Person",
+            "@id": "https://openlibrary.org/authors/OL21594A",
+            "url": "https://openlibrary.org/authors/OL21594A",
+            "name": "Jane Austen",
+            "birthDate": "1775-12-16",
+            "deathDate": "1817-07-18",
+            "sameAs": [
+                "https://www.wikidata.org/wiki/Q36322",
+                "https://viaf.org/viaf/102333412",
+                "https://isni.org/isni/0000000122834400",
+                "https://en.wikipedia.org/wiki/Jane_Austen",
+            ],
+        }])
+    );
+}
+
+#[test]
+fn organizations_have_no_life_dates() {
+    let mut author = common::author("OL21594A");
+    author["name"] = json!("Modern Language Association");
+    author["entity_type"] = json!("org");
+
+    let book = json_ld(common::edition("OL7353617M"), author);
+
+    assert_eq!(book["author"][0]["@type"], "Organization");
+    assert!(book["author"][0].get("birthDate").is_none());
+    assert!(book["author"][0].get("sameAs").is_none());
+}
@@ -0,0 +1,71 @@
+use openlibrary_client::{Language, LanguageCode};
+
+fn language(key: &str) -> Language {
+    Language { key: String::from(key) }
+}
+
+#[test]
+fn table_is_sorted_with_unique_codes() {
+    let codes: Vec<&str> = LanguageCode::all().iter().map(|language| language.marc).collect();
+
+    // Strictly increasing, from_marc binary searches the table
+    for pair in codes.windows(2) {
+        assert!(pair[0] < pair[1], "{} is not before {}", pair[0], pair[1]);
+    }
+    for language in LanguageCode::all() {
+        assert_eq!(language.marc.len(), 3, "{}", language.marc);
+        assert_eq!(language.marc, language.marc.to_ascii_lowercase());
+    }
+}
+
+#[test]
+fn current_iso639_1_codes_are_unique() {
+    let mut codes: Vec<&str> = LanguageCode::all()
+        .iter()
+        .filter(|language| !language.obsolete)
+        .filter_map(|language| language.iso639_1)
+        .collect();
+    let count = codes.len();
+    codes.sort_unstable();
+    codes.dedup();
+
+    assert_eq!(codes.len(), count);
+}
+
+#[test]
+fn open_library_keys_resolve() {
+    let english = language("/languages/eng");
+
+    assert_eq!(english.code(), "eng");
+    assert_eq!(english.name(), Some("English"));
+    assert_eq!(english.iso639_1(), Some("en"));
+    assert_eq!(english.iso639_2b(), Some("eng"));
+
+    let french = language("/languages/fre");
+    assert_eq!(french.iso639_1(), Some("fr"));
+    assert_eq!(french.iso639_2b(), Some("fre"));
+
+    let unknown = language("/languages/xxx");
+    assert_eq!(unknown.name(), None);
+    assert_eq!(unknown.iso639_1(), None);
+}
+
+#[test]
+fn obsolete_codes_map_forward() {
+    let croatian = LanguageCode::from_marc("scr").unwrap();
+    assert!(croatian.obsolete);
+    assert_eq!(croatian.iso639_2b, "hrv");
+    assert_eq!(croatian.iso639_1, Some("hr"));
+
+    let esperanto = language("/languages/esp");
+    assert_eq!(esperanto.iso639_2b(), Some("epo"));
+    assert_eq!(esperanto.name(), Some("Esperanto"));
+}
+
+#[test]
+fn iso639_1_lookup_skips_obsolete_codes() {
+    assert_eq!(LanguageCode::from_iso639_1("HR").unwrap().marc, "hrv");
+    assert_eq!(LanguageCode::lookup("en").unwrap().marc, "eng");
+    assert_eq!(LanguageCode::lookup(" ENG ").unwrap().marc, "eng");
+    assert_eq!(LanguageCode::lookup("zz"), None);
+}
@@ -0,0 +1,228 @@
+use async_std::task::block_on;
+use mockito::{mock, Matcher};
+use serde_json::json;
+
+use openlibrary_client::{Client, Olid, Paging, Seed, Session};
+
+fn serve(path: &str, body: &str) -> mockito::Mock {
+    mock("GET", path)
+        .with_header("Content-Type", "application/json")
+        .with_body(body)
+        .create()
+}
+
+#[test]
+fn user_lists_pages_through_the_summaries() {
+    let _lists = serve(
+        "/people/george08/lists.json?limit=20&offset=40",
+        r#"{
+            "links": { "self": "/people/george08/lists.json?limit=20&offset=40", "next": "/people/george08/lists.json?limit=20&offset=60" },
+            "size": 61,
+            "entries": [{
+                "url": "/people/george08/lists/OL97L",
+                "full_url": "/people/george08/lists/OL97L/Classics",
+                "name": "Classics",
+                "seed_count": 2,
+                "edition_count": 12,
+                "last_update": "2021-09-22T17:22:29.000000"
+            }]
+        }"#,
+    );
+
+    let client = Client::with_base_url(&mockito::server_url());
+    let page = block_on(client.user_lists("george08", Paging::new(20, 40))).unwrap();
+
+    assert!(page.has_next());
+    assert_eq!(page.size, 61);
+    assert_eq!(page.entries[0].name, "Classics");
+    assert_eq!(page.entries[0].seed_count, 2);
+}
+
+#[test]
+fn user_lists_escapes_the_username() {
+    let _lists = serve(
+        "/people/jane%20doe%3F/lists.json?limit=50&offset=0",
+        r#"{ "links": { "self": "" }, "size": 0, "entries": [] }"#,
+    );
+
+    let client = Client::with_base_url(&mockito::server_url());
+    let page = block_on(client.user_lists("jane doe?", Paging::default())).unwrap();
+
+    assert!(!page.has_next());
+    assert!(page.entries.is_empty());
+}
+
+#[test]
+fn list_accepts_a_full_url() {
+    let _list = serve(
+        "/people/george08/lists/OL98L.json",
+        r#"{
+            "key": "/people/george08/lists/OL98L",
+            "name": "Classics",
+            "type": { "key": "/type/list" },
+            "seeds": [{ "key": "/works/OL66554W" }, "subject:love"],
+            "revision": 2
+        }"#,
+    );
+
+    let client = Client::with_base_url(&mockito::server_url());
+    let list = block_on(client.list("/people/george08/lists/OL98L/Classics")).unwrap();
+
+    assert_eq!(list.get_olid(), "OL98L");
+    assert_eq!(list.seeds[0].key(), "/works/OL66554W");
+    assert_eq!(list.seeds[1], Seed::subject("love"));
+}
+
+#[test]
+fn list_seeds_reads_the_seed_entries() {
+    let _seeds = serve(
+        "/people/george08/lists/OL99L/seeds.json?limit=50&offset=0",
+        r#"{
+            "links": { "self": "/people/george08/lists/OL99L/seeds.json" },
+            "size": 1,
+            "entries": [{
+                "url": "/works/OL66554W",
+                "full_url": "/works/OL66554W/Pride_and_Prejudice",
+                "type": "work",
+                "title": "Pride and Prejudice",
+                "edition_count": 3,
+                "picture": { "url": "https://covers.openlibrary.org/b/id/14348537-S.jpg" }
+            }]
+        }"#,
+    );
+
+    let client = Client::with_base_url(&mockito::server_url());
+    let page = block_on(client.list_seeds("/people/george08/lists/OL99L", Paging::default())).unwrap();
+
+    let seed = &page.entries[0];
+    assert_eq!(seed.type_field, "work");
+    assert_eq!(seed.edition_count, Some(3));
+    assert_eq!(seed.picture.as_ref().unwrap().url, "https://covers.openlibrary.org/b/id/14348537-S.jpg");
+}
+
+#[test]
+fn list_editions_reads_edition_records() {
+    let _editions = serve(
+        "/people/george08/lists/OL100L/editions.json?limit=10&offset=0",
+        r#"{
+            "links": { "self": "/people/george08/lists/OL100L/editions.json" },
+            "size": 1,
+            "entries": [{
+                "key": "/books/OL7353617M",
+                "title": "Pride and Prejudice",
+                "type": { "key": "/type/edition" },
+                "revision": 3,
+                "created": { "type": "/type/datetime", "value": "2008-04-01T03:28:50.625462" },
+                "last_modified": { "type": "/type/datetime", "value": "2010-04-14T02:53:24.620268" }
+            }]
+        }"#,
+    );
+
+    let client = Client::with_base_url(&mockito::server_url());
+    let page = block_on(client.list_editions("people/george08/lists/OL100L.json", Paging::new(10, 0))).unwrap();
+
+    assert_eq!(page.entries[0].key, "/books/OL7353617M");
+    assert_eq!(page.entries[0].title, "Pride and Prejudice");
+}
+
+#[test]
+fn list_subjects_defaults_missing_groups() {
+    let _subjects = serve(
+        "/people/george08/lists/OL101L/subjects.json",
+        r#"{
+            "subjects": [{ "name": "Love", "count": 4, "url": "/subjects/love" }],
+            "places": [{ "name": "England", "count": 2, "url": "/subjects/place:england" }]
+        }"#,
+    );
+
+    let client = Client::with_base_url(&mockito::server_url());
+    let subjects = block_on(client.list_subjects("/people/george08/lists/OL101L")).unwrap();
+
+    assert_eq!(subjects.subjects[0].name, "Love");
+    assert_eq!(subjects.places[0].count, 2);
+    assert!(subjects.people.is_empty());
+    assert!(subjects.times.is_empty());
+}
+
+const LIST_OWNER_COOKIE: &str = "session=/people/george08%2C2021-09-22T17%3A22%3A29%2C7f3a9c";
+
+fn logged_in() -> Client {
+    let mut client = Client::with_base_url(&mockito::server_url());
+    client.set_session(Session::from_cookie(LIST_OWNER_COOKIE));
+    client
+}
+
+#[test]
+fn create_list_posts_the_list_and_returns_its_key() {
+    let create = mock("POST", "/people/george08/lists.json")
+        .match_header("Cookie", LIST_OWNER_COOKIE)
+        .match_body(Matcher::Json(json!({
+            "name": "Classics",
+            "description": "Books everyone should read",
+            "tags": [],
+            "seeds": [{ "key": "/works/OL66554W" }, "subject:love"],
+        })))
+        .with_header("Content-Type", "application/json")
+        .with_body(r#"{ "key": "/people/george08/lists/OL120L", "revision": 1 }"#)
+        .create();
+
+    let seeds = vec![Seed::from(Olid::Work(String::from("OL66554W"))), Seed::subject("love")];
+    let key = block_on(logged_in().create_list("Classics", "Books everyone should read", &seeds)).unwrap();
+
+    create.assert();
+    assert_eq!(key, "/people/george08/lists/OL120L");
+}
+
+#[test]
+fn add_seeds_posts_only_additions() {
+    let add = mock("POST", "/people/george08/lists/OL121L/seeds.json")
+        .match_header("Cookie", LIST_OWNER_COOKIE)
+        .match_body(Matcher::Json(json!({
+            "add": [{ "key": "/books/OL7353617M" }],
+            "remove": [],
+        })))
+        .with_body("{}")
+        .create();
+
+    let seeds = vec![Seed::from(Olid::Edition(String::from("OL7353617M")))];
+    block_on(logged_in().add_seeds("/people/george08/lists/OL121L", &seeds)).unwrap();
+
+    add.assert();
+}
+
+#[test]
+fn remove_seeds_posts_only_removals() {
+    let remove = mock("POST", "/people/george08/lists/OL122L/seeds.json")
+        .match_header("Cookie", LIST_OWNER_COOKIE)
+        .match_body(Matcher::Json(json!({
+            "add": [],
+            "remove": ["place:london"],
+        })))
+        .with_body("{}")
+        .create();
+
+    block_on(logged_in().remove_seeds("/people/george08/lists/OL122L/Classics", &[Seed::place("london")])).unwrap();
+
+    remove.assert();
+}
+
+#[test]
+fn delete_list_posts_to_the_delete_endpoint() {
+    let delete = mock("POST", "/people/george08/lists/OL123L/delete.json")
+        .match_header("Cookie", LIST_OWNER_COOKIE)
+        .with_body("{}")
+        .create();
+
+    block_on(logged_in().delete_list("people/george08/lists/OL123L.json")).unwrap();
+
+    delete.assert();
+}
+
+#[test]
+fn list_writes_need_a_login() {
+    let client = Client::with_base_url(&mockito::server_url());
+
+    let err = block_on(client.delete_list("/people/george08/lists/OL124L")).unwrap_err();
+
+    assert_eq!(err.status(), 401);
+}
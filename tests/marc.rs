@@ -0,0 +1,151 @@
+mod common;
+
+use serde_json::json;
+
+use openlibrary_client::{parse_marc21, Edition, MarcDataField, MarcError, MarcRecord};
+
+const FIELD_TERMINATOR: u8 = 0x1E;
+const RECORD_TERMINATOR: u8 = 0x1D;
+
+fn number(bytes: &[u8]) -> usize {
+    std::str::from_utf8(bytes).unwrap().parse().unwrap()
+}
+
+fn record() -> MarcRecord {
+    let mut record = MarcRecord::default();
+    record.control("001", "OL7353617M");
+    record.control("008", "080401s2002    enk           000 1 eng d");
+    record.data(MarcDataField::new("020", ' ', ' ').subfield('a', "9780141439518"));
+    record.data(MarcDataField::new("100", '1', ' ').subfield('a', "Austen, Jane,").subfield('d', "1775-1817."));
+    // Multibyte characters, so byte and character counts differ
+    record.data(MarcDataField::new("245", '1', '0').subfield('a', "Orgueil et préjugés /").subfield('c', "Jane Austen ; traduit par Éloïse Perks."));
+    record
+}
+
+#[test]
+fn leader_holds_record_length_and_base_address() {
+    let record = record();
+    let bytes = record.to_marc21().unwrap();
+    let fields = record.control_fields.len() + record.data_fields.len();
+
+    assert_eq!(number(&bytes[0..5]), bytes.len());
+    assert_eq!(number(&bytes[12..17]), 24 + 12 * fields + 1);
+    // Unicode, two indicators, two character subfield codes and the fixed entry map
+    assert_eq!(bytes[9], b'a');
+    assert_eq!(&bytes[10..12], b"22");
+    assert_eq!(&bytes[20..24], b"4500");
+}
+
+#[test]
+fn directory_entries_locate_each_field() {
+    let record = record();
+    let bytes = record.to_marc21().unwrap();
+    let base_address = number(&bytes[12..17]);
+    let directory = &bytes[24..base_address - 1];
+    assert_eq!(bytes[base_address - 1], FIELD_TERMINATOR);
+    assert_eq!(directory.len() % 12, 0);
+
+    let tags: Vec<&str> = record
+        .control_fields
+        .iter()
+        .map(|field| field.tag.as_str())
+        .chain(record.data_fields.iter().map(|field| field.tag.as_str()))
+        .collect();
+
+    let mut expected_start = 0;
+    for (entry, tag) in directory.chunks(12).zip(tags) {
+        assert_eq!(&entry[0..3], tag.as_bytes());
+        let length = number(&entry[3..7]);
+        let start = number(&entry[7..12]);
+        assert_eq!(start, expected_start, "field {} does not follow the previous one", tag);
+
+        let field = &bytes[base_address + start..base_address + start + length];
+        assert_eq!(field.last(), Some(&FIELD_TERMINATOR), "field {} is not terminated", tag);
+        assert!(!field[..length - 1].contains(&FIELD_TERMINATOR));
+        expected_start += length;
+    }
+
+    // The last field is followed only by the record terminator
+    assert_eq!(base_address + expected_start + 1, bytes.len());
+    assert_eq!(bytes.last(), Some(&RECORD_TERMINATOR));
+}
+
+#[test]
+fn written_records_parse_back() {
+    let record = record();
+    let mut bytes = record.to_marc21().unwrap();
+    bytes.extend(record.to_marc21().unwrap());
+
+    let parsed = parse_marc21(&bytes).unwrap();
+
+    assert_eq!(parsed.len(), 2);
+    for parsed in parsed {
+        assert_eq!(parsed.leader.as_bytes(), &bytes[..24]);
+        assert_eq!(parsed.control_fields, record.control_fields);
+        assert_eq!(parsed.data_fields, record.data_fields);
+    }
+}
+
+// An ISO 2709 record with the given leader position 9 and raw field data
+fn raw_record(encoding: u8, fields: &[(&str, &[u8])]) -> Vec<u8> {
+    let mut directory = Vec::new();
+    let mut data = Vec::new();
+    for (tag, field) in fields {
+        directory.extend_from_slice(format!("{}{:04}{:05}", tag, field.len() + 1, data.len()).as_bytes());
+        data.extend_from_slice(field);
+        data.push(FIELD_TERMINATOR);
+    }
+    directory.push(FIELD_TERMINATOR);
+
+    let base_address = 24 + directory.len();
+    let length = base_address + data.len() + 1;
+    let mut record = format!("{:05}nam {}2200000   4500", length, encoding as char).into_bytes();
+    record[12..17].copy_from_slice(format!("{:05}", base_address).as_bytes());
+    record.extend(directory);
+    record.extend(data);
+    record.push(RECORD_TERMINATOR);
+    record
+}
+
+#[test]
+fn marc8_records_are_transcoded() {
+    // "Orgueil et pr\u{e9}jug\u{e9}s" and "\u{141}\u{f3}d\u{17a}", diacritics before their letter
+    let bytes = raw_record(
+        b' ',
+        &[
+            ("001", b"OL1M"),
+            ("245", b"10\x1FaOrgueil et pr\xE2ejug\xE2es /\x1Fc\xA1\xE2od\xE7z."),
+        ],
+    );
+
+    let record = MarcRecord::from_marc21(&bytes).unwrap();
+    let title = record.fields("245").next().unwrap();
+
+    assert_eq!(title.get('a'), Some("Orgueil et pre\u{301}juge\u{301}s /"));
+    assert_eq!(title.get('c'), Some("\u{141}o\u{301}dz\u{307}."));
+}
+
+#[test]
+fn unreadable_encodings_are_errors() {
+    let escaped = raw_record(b' ', &[("245", b"10\x1Fa\x1B(SGreek\x1B(B")]);
+    let unknown = raw_record(b'z', &[("245", b"10\x1FaTitle")]);
+
+    for bytes in [escaped, unknown].iter() {
+        match MarcRecord::from_marc21(bytes) {
+            Err(MarcError::UnsupportedEncoding(_)) => {}
+            other => panic!("expected an unsupported encoding, got {:?}", other),
+        }
+    }
+}
+
+#[test]
+fn edition_statement_is_trimmed_and_blank_ones_left_out() {
+    let mut edition = common::edition("OL7353617M");
+    edition["edition_name"] = json!(" 2nd ed. ");
+    let record = common::from_value::<Edition>(edition.clone()).to_marc();
+    assert_eq!(record.fields("250").next().and_then(|field| field.get('a')), Some("2nd ed."));
+
+    edition["edition_name"] = json!("");
+    let record = common::from_value::<Edition>(edition).to_marc();
+    assert_eq!(record.fields("250").count(), 0);
+}
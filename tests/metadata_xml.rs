@@ -0,0 +1,214 @@
+mod common;
+
+use quick_xml::events::Event;
+use serde_json::json;
+
+use openlibrary_client::{Author, Edition, Entity, Work};
+
+const DC_ORDER: [&str; 12] = [
+    "dc:title",
+    "dc:creator",
+    "dc:contributor",
+    "dc:subject",
+    "dc:description",
+    "dc:publisher",
+    "dc:date",
+    "dc:type",
+    "dc:format",
+    "dc:identifier",
+    "dc:language",
+    "dc:relation",
+];
+
+// Top level MODS 3.7 elements in the order the writer emits them
+const MODS_ORDER: [&str; 15] = [
+    "titleInfo",
+    "name",
+    "typeOfResource",
+    "genre",
+    "originInfo",
+    "language",
+    "physicalDescription",
+    "abstract",
+    "tableOfContents",
+    "subject",
+    "classification",
+    "relatedItem",
+    "identifier",
+    "location",
+    "recordInfo",
+];
+
+struct Element {
+    // Names from the root down to this element
+    path: Vec<String>,
+    attributes: Vec<(String, String)>,
+}
+
+impl Element {
+    fn name(&self) -> &str {
+        self.path.last().unwrap()
+    }
+
+    fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes.iter().find(|(key, _)| key == name).map(|(_, value)| value.as_str())
+    }
+}
+
+fn elements(xml: &str) -> Vec<Element> {
+    let mut reader = quick_xml::Reader::from_str(xml);
+    let mut elements = Vec::new();
+    let mut path: Vec<String> = Vec::new();
+
+    loop {
+        let (element, empty) = match reader.read_event().unwrap() {
+            Event::Start(element) => (element, false),
+            Event::Empty(element) => (element, true),
+            Event::End(_) => {
+                path.pop();
+                continue;
+            }
+            Event::Eof => break,
+            _ => continue,
+        };
+
+        path.push(String::from_utf8(element.name().as_ref().to_vec()).unwrap());
+        let attributes = element
+            .attributes()
+            .map(|attribute| {
+                let attribute = attribute.unwrap();
+                let key = String::from_utf8(attribute.key.as_ref().to_vec()).unwrap();
+                (key, String::from_utf8(attribute.value.to_vec()).unwrap())
+            })
+            .collect();
+        elements.push(Element { path: path.clone(), attributes });
+        if empty {
+            path.pop();
+        }
+    }
+
+    elements
+}
+
+fn edition() -> serde_json::Value {
+    let mut edition = common::edition("OL7353617M");
+    let details = json!({
+        "title": "The Annotated Pride and Prejudice",
+        "subtitle": "A Novel",
+        "contributions": ["Edited by David M. Shapard"],
+        "publishers": ["Anchor Books"],
+        "publish_places": ["New York"],
+        "publish_country": "nyu",
+        "publish_date": "2012",
+        "physical_format": "Paperback",
+        "number_of_pages": 740,
+        "physical_dimensions": "20 x 13 x 4 centimeters",
+        "languages": [{ "key": "/languages/eng" }],
+        "subjects": ["Courtship"],
+        "series": ["Anchor classics"],
+        "lc_classifications": ["PR4034 .P7 2012"],
+        "isbn_13": ["9780307950901"],
+        "description": "An annotated edition.",
+        "table_of_contents": [{ "level": 0, "title": "Volume I", "type": { "key": "/type/toc_item" } }],
+    });
+    for (field, value) in details.as_object().unwrap() {
+        edition[field] = value.clone();
+    }
+
+    edition
+}
+
+fn entity() -> Entity {
+    entity_of(edition())
+}
+
+fn entity_of(edition: serde_json::Value) -> Entity {
+    let edition: Edition = common::from_value(edition);
+    let work: Work = common::from_value(common::work("OL66554W"));
+    let author: Author = common::from_value(common::author("OL21594A"));
+
+    Entity::new(String::from("OL7353617M"), edition, work, author)
+}
+
+// `children` holds every element of `order` and nothing else, in that order
+fn assert_ordered(children: &[&str], order: &[&str]) {
+    let positions: Vec<usize> = children
+        .iter()
+        .map(|child| order.iter().position(|name| name == child).unwrap_or_else(|| panic!("unexpected element {}", child)))
+        .collect();
+
+    assert!(positions.windows(2).all(|pair| pair[0] <= pair[1]), "out of order: {:?}", children);
+    for name in order {
+        assert!(children.contains(name), "missing {}", name);
+    }
+}
+
+#[test]
+fn dublin_core_is_a_flat_oai_dc_record() {
+    let xml = entity().to_dublin_core_xml();
+    let elements = elements(&xml);
+
+    let root = &elements[0];
+    assert_eq!(root.name(), "oai_dc:dc");
+    assert_eq!(root.attribute("xmlns:oai_dc"), Some("http://www.openarchives.org/OAI/2.0/oai_dc/"));
+    assert_eq!(root.attribute("xmlns:dc"), Some("http://purl.org/dc/elements/1.1/"));
+    assert!(root.attribute("xsi:schemaLocation").unwrap().ends_with("oai_dc.xsd"));
+
+    // Only dc elements, directly under the root
+    assert!(elements[1..].iter().all(|element| element.path.len() == 2));
+    let children: Vec<&str> = elements[1..].iter().map(Element::name).collect();
+    assert_ordered(&children, &DC_ORDER);
+}
+
+#[test]
+fn mods_follows_the_3_7_schema() {
+    let xml = entity().to_mods_xml();
+    let elements = elements(&xml);
+
+    let root = &elements[0];
+    assert_eq!(root.name(), "mods");
+    assert_eq!(root.attribute("xmlns"), Some("http://www.loc.gov/mods/v3"));
+    assert_eq!(root.attribute("version"), Some("3.7"));
+    assert!(root.attribute("xsi:schemaLocation").unwrap().ends_with("mods-3-7.xsd"));
+
+    let children: Vec<&str> = elements.iter().filter(|element| element.path.len() == 2).map(Element::name).collect();
+    assert_ordered(&children, &MODS_ORDER);
+
+    // Leaf elements only appear inside their wrappers
+    let wrappers = [
+        ("nonSort", "mods/titleInfo"),
+        ("subTitle", "mods/titleInfo"),
+        ("namePart", "mods/name"),
+        ("roleTerm", "mods/name/role"),
+        ("placeTerm", "mods/originInfo/place"),
+        ("publisher", "mods/originInfo"),
+        ("dateIssued", "mods/originInfo"),
+        ("languageTerm", "mods/language"),
+        ("form", "mods/physicalDescription"),
+        ("extent", "mods/physicalDescription"),
+        ("topic", "mods/subject"),
+        ("url", "mods/location"),
+        ("recordIdentifier", "mods/recordInfo"),
+    ];
+    for (name, parent) in wrappers.iter() {
+        let found: Vec<&Element> = elements.iter().filter(|element| element.name() == *name).collect();
+        assert!(!found.is_empty(), "missing {}", name);
+        for element in found {
+            assert_eq!(element.path[..element.path.len() - 1].join("/"), *parent, "{} is misplaced", name);
+        }
+    }
+    let titles: Vec<&Element> = elements.iter().filter(|element| element.name() == "title").collect();
+    assert_eq!(titles[0].path.join("/"), "mods/titleInfo/title");
+    assert_eq!(titles[1].path.join("/"), "mods/relatedItem/titleInfo/title");
+}
+
+#[test]
+fn mods_edition_is_trimmed_and_blank_ones_left_out() {
+    let mut edition = edition();
+    edition["edition_name"] = json!(" Annotated ed. ");
+    assert!(entity_of(edition.clone()).to_mods_xml().contains("<edition>Annotated ed.</edition>"));
+
+    edition["edition_name"] = json!(" ");
+    let elements = elements(&entity_of(edition).to_mods_xml());
+    assert!(elements.iter().all(|element| element.name() != "edition"));
+}
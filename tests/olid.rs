@@ -0,0 +1,33 @@
+use openlibrary_client::{InvalidOlid, Olid};
+
+#[test]
+fn parses_ids_and_keys() {
+    let cases = [
+        ("OL7353617M", Olid::Edition(String::from("OL7353617M"))),
+        ("OL66554W", Olid::Work(String::from("OL66554W"))),
+        ("OL21594A", Olid::Author(String::from("OL21594A"))),
+        ("/books/OL7353617M", Olid::Edition(String::from("OL7353617M"))),
+        ("/works/OL66554W.json", Olid::Work(String::from("OL66554W"))),
+        (" /authors/OL21594A ", Olid::Author(String::from("OL21594A"))),
+    ];
+
+    for (input, expected) in cases {
+        assert_eq!(input.parse::<Olid>(), Ok(expected), "{}", input);
+    }
+}
+
+#[test]
+fn rejects_malformed_ids() {
+    for input in ["", "OL", "OLM", "OL1", "OL1X", "ol1M", "OLxM", "OL1 2M", "OL1é", "OLé1M", "/people/george08/lists/OL97L"] {
+        assert_eq!(input.parse::<Olid>(), Err(InvalidOlid(String::from(input))), "{:?}", input);
+    }
+}
+
+#[test]
+fn key_and_display_round_trip() {
+    let olid: Olid = "/books/OL7353617M".parse().unwrap();
+
+    assert_eq!(olid.key(), "/books/OL7353617M");
+    assert_eq!(olid.to_string(), "OL7353617M");
+    assert_eq!(olid.key().parse::<Olid>(), Ok(olid));
+}
@@ -0,0 +1,120 @@
+mod common;
+
+use serde_json::{json, Value};
+
+use openlibrary_client::{write_onix, Author, Edition, Entity, Work};
+
+fn entity(edition: Value, authors: Vec<Value>) -> Entity {
+    let edition: Edition = common::from_value(edition);
+    let work: Work = common::from_value(common::work("OL45804W"));
+    let authors: Vec<Author> = authors.into_iter().map(common::from_value).collect();
+
+    Entity::with_authors(String::from("OL7353617M"), edition, work, authors)
+}
+
+// The message without its indentation, so expectations can span elements
+fn onix(entity: &Entity) -> String {
+    entity.to_onix("Open Library").lines().map(str::trim).collect()
+}
+
+fn contributor(sequence: usize, role: &str, name: &str) -> String {
+    format!(
+        "<Contributor><SequenceNumber>{}</SequenceNumber><ContributorRole>{}</ContributorRole><PersonName>{}</PersonName></Contributor>",
+        sequence, role, name
+    )
+}
+
+#[test]
+fn isbn_10_is_converted_to_the_isbn_13_identifier() {
+    let mut edition = common::edition("OL7353617M");
+    edition["isbn_10"] = json!(["0-14-032872-6"]);
+
+    let onix = onix(&entity(edition.clone(), Vec::new()));
+    assert!(onix.contains(
+        "<ProductIdentifier><ProductIDType>15</ProductIDType><IDValue>9780140328721</IDValue></ProductIdentifier>"
+    ));
+    assert!(onix.contains(
+        "<ProductIdentifier><ProductIDType>01</ProductIDType><IDTypeName>Open Library</IDTypeName><IDValue>OL7353617M</IDValue></ProductIdentifier>"
+    ));
+
+    // An ISBN-13 on the record wins over the converted ISBN-10
+    edition["isbn_13"] = json!(["978-0-14-032872-1"]);
+    edition["isbn_10"] = json!(["0140328726"]);
+    let onix = self::onix(&entity(edition, Vec::new()));
+    assert_eq!(onix.matches("<ProductIDType>15</ProductIDType>").count(), 1);
+    assert!(onix.contains("<IDValue>9780140328721</IDValue>"));
+}
+
+#[test]
+fn contributions_get_their_role_codes() {
+    let mut edition = common::edition("OL7353617M");
+    edition["contributions"] = json!([
+        "Illustrated by Quentin Blake",
+        "Jane Smith (Translator)",
+        "Introduction: Michael Rosen",
+        "Narrated By Chris O'Dowd",
+        "Somebody Else",
+        "  ",
+    ]);
+    let mut author = common::author("OL34184A");
+    author["name"] = json!("Roald Dahl");
+
+    let onix = onix(&entity(edition, vec![author]));
+
+    assert!(onix.contains("<SequenceNumber>1</SequenceNumber><ContributorRole>A01</ContributorRole><PersonName>Roald Dahl</PersonName>"));
+    assert!(onix.contains(&contributor(2, "A12", "Quentin Blake")));
+    assert!(onix.contains(&contributor(3, "B06", "Jane Smith")));
+    assert!(onix.contains(&contributor(4, "A24", "Michael Rosen")));
+    assert!(onix.contains(&contributor(5, "E03", "Chris O&apos;Dowd")));
+    assert!(onix.contains(&contributor(6, "Z99", "Somebody Else")));
+    assert_eq!(onix.matches("<Contributor>").count(), 6);
+    assert!(!onix.contains("<NoContributor/>"));
+}
+
+#[test]
+fn no_contributor_is_stated_explicitly() {
+    let mut author = common::author("OL34184A");
+    author["name"] = json!(" ");
+
+    let onix = onix(&entity(common::edition("OL7353617M"), vec![author]));
+
+    assert!(onix.contains("</TitleDetail><NoContributor/>"));
+    assert!(!onix.contains("<Contributor>"));
+}
+
+#[test]
+fn leading_articles_go_into_the_title_prefix() {
+    let mut edition = common::edition("OL7353617M");
+    edition["title"] = json!("The BFG");
+    let onix = onix(&entity(edition.clone(), Vec::new()));
+    assert!(onix.contains("<TitleElementLevel>01</TitleElementLevel><TitlePrefix>The</TitlePrefix><TitleWithoutPrefix>BFG</TitleWithoutPrefix>"));
+
+    // Only whole words are articles
+    edition["title"] = json!("Theodore Boone");
+    let onix = self::onix(&entity(edition, Vec::new()));
+    assert!(onix.contains("<TitleText>Theodore Boone</TitleText>"));
+    assert!(!onix.contains("<TitlePrefix>"));
+}
+
+#[test]
+fn blank_edition_statement_is_left_out() {
+    let mut edition = common::edition("OL7353617M");
+    edition["edition_name"] = json!(" 1st ed. ");
+    assert!(onix(&entity(edition.clone(), Vec::new())).contains("<EditionStatement>1st ed.</EditionStatement>"));
+
+    edition["edition_name"] = json!("  ");
+    assert!(!onix(&entity(edition, Vec::new())).contains("<EditionStatement>"));
+}
+
+#[test]
+fn feed_holds_a_product_per_entity() {
+    let entities = vec![entity(common::edition("OL7353617M"), Vec::new()), entity(common::edition("OL7353618M"), Vec::new())];
+
+    let mut written = Vec::new();
+    write_onix(&mut written, "Open Library", &entities).unwrap();
+    let written = String::from_utf8(written).unwrap();
+
+    assert_eq!(written.matches("<ONIXMessage ").count(), 1);
+    assert_eq!(written.matches("<Header>").count(), 1);
+    assert_eq!(written.matches("<Product>").count(), 2);
+}
@@ -0,0 +1,66 @@
+mod common;
+
+use std::collections::BTreeMap;
+
+use serde_json::json;
+
+use openlibrary_client::{Author, Availability, Edition, Entity, OpdsFeed, Work};
+
+const OPEN_ACCESS: &str = "http://opds-spec.org/acquisition/open-access";
+const BORROW: &str = "http://opds-spec.org/acquisition/borrow";
+
+fn feed(status: Option<&str>) -> OpdsFeed {
+    let mut edition: Edition = common::from_value(common::edition("OL7353617M"));
+    edition.ocaid = Some(String::from("prideprejudice00aust"));
+    let work: Work = common::from_value(common::work("OL66554W"));
+    let author: Author = common::from_value(common::author("OL21594A"));
+    let entity = Entity::new(String::from("OL7353617M"), edition, work, author);
+
+    let mut availability = BTreeMap::new();
+    if let Some(status) = status {
+        let item: Availability = serde_json::from_value(json!({
+            "records": {},
+            "items": [{
+                "status": status,
+                "match": "exact",
+                "itemURL": "https://archive.org/details/prideprejudice00aust",
+                "fromRecord": "/books/OL7353617M",
+            }],
+        }))
+        .unwrap();
+        availability.insert(String::from("olid:OL7353617M"), item);
+    }
+
+    OpdsFeed::new("Austen", "https://example.org/opds", vec![entity]).with_availability(availability)
+}
+
+fn acquisition_rels(feed: &OpdsFeed) -> Vec<String> {
+    feed.to_opds2()["publications"][0]["links"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|link| link["rel"].as_str().unwrap().to_string())
+        .filter(|rel| rel.starts_with("http://opds-spec.org/acquisition"))
+        .collect()
+}
+
+#[test]
+fn public_scans_get_open_access_downloads() {
+    let feed = feed(Some("full access"));
+
+    assert_eq!(acquisition_rels(&feed), vec![OPEN_ACCESS, OPEN_ACCESS]);
+    let atom = feed.to_opds1();
+    assert!(atom.contains("prideprejudice00aust.epub"));
+    assert!(atom.contains("prideprejudice00aust.pdf"));
+    assert!(!atom.contains(BORROW));
+}
+
+#[test]
+fn lending_scans_only_get_a_borrow_link() {
+    for status in [Some("lendable"), Some("checked out"), None].iter() {
+        let feed = feed(*status);
+
+        assert_eq!(acquisition_rels(&feed), vec![BORROW], "{:?}", status);
+        assert!(!feed.to_opds1().contains(".epub"));
+    }
+}
@@ -0,0 +1,29 @@
+mod common;
+
+use openlibrary_client::{Dimensions, Edition, LengthUnit};
+
+fn weighing(weight: &str) -> Option<f64> {
+    let mut edition: Edition = common::from_value(common::edition("OL7353617M"));
+    edition.weight = Some(String::from(weight));
+    edition.weight_grams()
+}
+
+#[test]
+fn commas_are_thousands_separators_or_decimal_commas() {
+    assert_eq!(weighing("1,250 grams"), Some(1250.0));
+    assert_eq!(weighing("1,250.5 grams"), Some(1250.5));
+    assert_eq!(weighing("1,2 kg"), Some(1200.0));
+    assert_eq!(weighing("0,75 kg"), Some(750.0));
+
+    let dimensions = Dimensions::parse("1,5 x 2,25 x 1,000 mm").unwrap();
+    assert_eq!((dimensions.height, dimensions.width, dimensions.depth), (1.5, Some(2.25), Some(1000.0)));
+}
+
+#[test]
+fn dimensions_need_a_known_unit() {
+    let dimensions = Dimensions::parse("9.2 x 6.1 x 0.8 inches").unwrap();
+    assert_eq!(dimensions.unit, LengthUnit::Inches);
+
+    assert_eq!(Dimensions::parse("24 x 16 x 2 furlongs"), None);
+    assert_eq!(Dimensions::parse("24 x 16"), None);
+}
@@ -0,0 +1,44 @@
+use async_std::task::block_on;
+use mockito::mock;
+
+use openlibrary_client::Client;
+
+#[test]
+fn work_ratings_reads_the_summary_and_counts() {
+    let _ratings = mock("GET", "/works/OL45804W/ratings.json")
+        .with_header("Content-Type", "application/json")
+        .with_body(
+            r#"{
+                "summary": { "average": 3.84, "count": 25, "sortable": 3.42 },
+                "counts": { "1": 1, "2": 2, "3": 5, "4": 8, "5": 9 }
+            }"#,
+        )
+        .create();
+
+    let client = Client::with_base_url(&mockito::server_url());
+    let ratings = block_on(client.work_ratings("/works/OL45804W")).unwrap();
+
+    assert_eq!(ratings.summary.average, Some(3.84));
+    assert_eq!(ratings.summary.count, 25);
+    assert_eq!(ratings.counts.distribution(), [1, 2, 5, 8, 9]);
+}
+
+#[test]
+fn unrated_work_has_no_average() {
+    let _ratings = mock("GET", "/works/OL1W/ratings.json")
+        .with_header("Content-Type", "application/json")
+        .with_body(
+            r#"{
+                "summary": { "average": null, "count": 0 },
+                "counts": { "1": 0, "2": 0, "3": 0, "4": 0, "5": 0 }
+            }"#,
+        )
+        .create();
+
+    let client = Client::with_base_url(&mockito::server_url());
+    let ratings = block_on(client.work_ratings("OL1W")).unwrap();
+
+    assert_eq!(ratings.summary.average, None);
+    assert_eq!(ratings.summary.sortable, None);
+    assert_eq!(ratings.counts.stars(5), Some(0));
+}
@@ -0,0 +1,78 @@
+use async_std::task::block_on;
+use mockito::mock;
+
+use openlibrary_client::{Client, ReadKey, ReadStatus};
+
+const BRIEF: &str = r#"{
+    "records": {
+        "/books/OL7353617M": {
+            "isbns": ["0596156715"],
+            "olids": ["OL7353617M"],
+            "publishDates": ["2009"],
+            "recordURL": "https://openlibrary.org/books/OL7353617M/Pride_and_Prejudice"
+        }
+    },
+    "items": [{
+        "status": "full access",
+        "match": "exact",
+        "itemURL": "https://archive.org/details/prideprejudice00aust",
+        "fromRecord": "/books/OL7353617M",
+        "ol-edition-id": "OL7353617M"
+    }]
+}"#;
+
+fn serve(path: &str, body: &str) -> mockito::Mock {
+    mock("GET", path)
+        .with_header("Content-Type", "application/json")
+        .with_body(body)
+        .create()
+}
+
+#[test]
+fn single_key_uses_the_per_kind_path() {
+    let _brief = serve("/api/volumes/brief/isbn/0596156715.json", BRIEF);
+
+    let client = Client::with_base_url(&mockito::server_url());
+    let availability = block_on(client.read_availability(&[ReadKey::ISBN(String::from("0596156715"))])).unwrap();
+
+    let brief = &availability["isbn:0596156715"];
+    assert_eq!(brief.records["/books/OL7353617M"].isbns, vec![String::from("0596156715")]);
+    assert_eq!(brief.items[0].status, ReadStatus::FullAccess);
+    assert_eq!(brief.items[0].get_ocaid().as_deref(), Some("prideprejudice00aust"));
+    assert!(brief.readable_now());
+}
+
+#[test]
+fn several_keys_are_batched() {
+    let _brief = serve(
+        "/api/volumes/brief/json/olid:OL7353617M|lccn:2001022779",
+        &format!(r#"{{ "olid:OL7353617M": {}, "lccn:2001022779": {{ "records": {{}}, "items": [] }} }}"#, BRIEF),
+    );
+
+    let client = Client::with_base_url(&mockito::server_url());
+    let keys = [ReadKey::OLID(String::from("OL7353617M")), ReadKey::LCCN(String::from("2001022779"))];
+    let availability = block_on(client.read_availability(&keys)).unwrap();
+
+    assert_eq!(availability.len(), 2);
+    assert!(availability["olid:OL7353617M"].readable_now());
+    assert!(!availability["lccn:2001022779"].readable_now());
+}
+
+#[test]
+fn key_values_are_escaped() {
+    let _brief = serve("/api/volumes/brief/oclc/297222669%2F1%3F.json", r#"{ "records": {}, "items": [] }"#);
+
+    let client = Client::with_base_url(&mockito::server_url());
+    let availability = block_on(client.read_availability(&[ReadKey::OCLC(String::from("297222669/1?"))])).unwrap();
+
+    assert!(availability["oclc:297222669/1?"].items.is_empty());
+}
+
+#[test]
+fn no_keys_is_an_empty_result() {
+    // Nothing is served, a request would fail
+    let client = Client::with_base_url(&mockito::server_url());
+    let availability = block_on(client.read_availability(&[])).unwrap();
+
+    assert!(availability.is_empty());
+}
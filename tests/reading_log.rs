@@ -0,0 +1,47 @@
+use async_std::task::block_on;
+use mockito::mock;
+
+use openlibrary_client::{Client, ReadingLog, Shelf};
+
+#[test]
+fn edition_key_is_only_the_logged_edition() {
+    let log: ReadingLog = serde_json::from_str(
+        r#"{
+            "page": 1,
+            "numFound": 2,
+            "reading_log_entries": [
+                {
+                    "work": { "key": "/works/OL66554W", "title": "Pride and Prejudice", "cover_edition_key": "OL50958M" },
+                    "logged_edition": "/books/OL7353617M",
+                    "logged_date": "2021/09/22, 17:22:29"
+                },
+                {
+                    "work": { "key": "/works/OL262758W", "title": "Emma", "cover_edition_key": "OL24196937M" },
+                    "logged_date": "2021/09/22, 17:25:02"
+                }
+            ]
+        }"#,
+    )
+    .unwrap();
+
+    let chosen = &log.reading_log_entries[0];
+    assert_eq!(chosen.edition_key().as_deref(), Some("OL7353617M"));
+    assert_eq!(chosen.cover_edition_key().as_deref(), Some("OL50958M"));
+
+    let shelved = &log.reading_log_entries[1];
+    assert_eq!(shelved.edition_key(), None);
+    assert_eq!(shelved.cover_edition_key().as_deref(), Some("OL24196937M"));
+}
+
+#[test]
+fn reading_log_escapes_the_username_and_starts_at_page_one() {
+    let _log = mock("GET", "/people/jane%20doe/books/want-to-read.json?page=1")
+        .with_header("Content-Type", "application/json")
+        .with_body(r#"{ "page": 1, "numFound": 0, "reading_log_entries": [] }"#)
+        .create();
+
+    let client = Client::with_base_url(&mockito::server_url());
+    let log = block_on(client.reading_log("jane doe", Shelf::WantToRead, 0)).unwrap();
+
+    assert!(log.reading_log_entries.is_empty());
+}
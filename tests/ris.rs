@@ -0,0 +1,96 @@
+mod common;
+
+use serde_json::json;
+
+use openlibrary_client::{write_ris, Author, Edition, Entity, Work};
+
+fn author(name: &str) -> Author {
+    let mut author = common::author("OL21594A");
+    author["name"] = json!(name);
+    common::from_value(author)
+}
+
+fn entity(edition: serde_json::Value, authors: Vec<Author>) -> Entity {
+    let mut work = common::work("OL66554W");
+    work["subjects"] = json!(["Love stories", "England"]);
+    let edition: Edition = common::from_value(edition);
+    let work: Work = common::from_value(work);
+
+    Entity::with_authors(String::from("OL7353617M"), edition, work, authors)
+}
+
+#[test]
+fn record_lists_repeated_tags_in_order() {
+    let mut edition = common::edition("OL7353617M");
+    edition["subtitle"] = json!("A Novel");
+    edition["series"] = json!(["Penguin   Classics"]);
+    edition["publish_date"] = json!("1813");
+    edition["publishers"] = json!(["T. Egerton", "Penguin"]);
+    edition["publish_places"] = json!(["London"]);
+    edition["isbn_13"] = json!(["9780141439518"]);
+    edition["isbn_10"] = json!(["0141439513"]);
+    edition["edition_name"] = json!("  2nd\ted. ");
+    edition["subjects"] = json!(["Love stories", "Sisters\n  Fiction"]);
+
+    let entity = entity(edition, vec![author("Jane Austen"), author("Martin Luther King Jr.")]);
+
+    assert_eq!(
+        entity.to_ris(),
+        [
+            "TY  - BOOK",
+            "AU  - Austen, Jane",
+            "AU  - King, Martin Luther, Jr.",
+            "TI  - Pride and Prejudice: A Novel",
+            "T2  - Penguin Classics",
+            "PY  - 1813",
+            "PB  - T. Egerton",
+            "PB  - Penguin",
+            "CY  - London",
+            "SN  - 9780141439518",
+            "SN  - 0141439513",
+            "ET  - 2nd ed.",
+            "KW  - Love stories",
+            "KW  - Sisters Fiction",
+            "KW  - England",
+            "UR  - https://openlibrary.org/books/OL7353617M",
+            "ER  - ",
+            "",
+        ]
+        .join("\r\n")
+    );
+}
+
+#[test]
+fn empty_values_are_left_out() {
+    let mut edition = common::edition("OL7353617M");
+    edition["edition_name"] = json!(" ");
+    edition["publishers"] = json!([""]);
+    let mut organization = author("Modern Language Association");
+    organization.entity_type = Some(String::from("org"));
+
+    let ris = entity(edition, vec![organization]).to_ris();
+
+    assert!(ris.contains("AU  - Modern Language Association\r\n"));
+    assert!(!ris.contains("ET  -"));
+    assert!(!ris.contains("PB  -"));
+    assert!(!ris.contains("PY  -"));
+}
+
+#[test]
+fn records_are_separated_by_a_blank_line() {
+    let entities = vec![
+        entity(common::edition("OL7353617M"), vec![author("Jane Austen")]),
+        entity(common::edition("OL7353618M"), Vec::new()),
+    ];
+
+    let mut written = Vec::new();
+    write_ris(&mut written, &entities).unwrap();
+    let written = String::from_utf8(written).unwrap();
+
+    assert!(written.starts_with("TY  - BOOK\r\n"));
+    assert!(written.ends_with("ER  - \r\n"));
+    assert_eq!(written.matches("ER  - \r\n\r\nTY  - BOOK\r\n").count(), 1);
+    assert_eq!(written.matches("TY  - BOOK").count(), 2);
+    // Every line ends in CRLF
+    assert!(!written.replace("\r\n", "").contains(['\r', '\n']));
+}